@@ -0,0 +1,54 @@
+use wgpu_wayland_window::{App, RenderContext, WindowBuilder, WindowError};
+
+// ─────────────────────────────────────────────────────────────
+// Example App
+//
+// Clears the window to blue every frame.
+// ─────────────────────────────────────────────────────────────
+struct ClearApp {
+    color: wgpu::Color,
+}
+
+impl App for ClearApp {
+    fn render(&mut self, context: &mut RenderContext) {
+        let (encoder, view) = context.encoder_and_view();
+
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("clear_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(self.color),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+    }
+}
+
+fn main() -> Result<(), WindowError> {
+    // ─────────────────────────────────────────────────────────────
+    // Logging
+    //
+    // wgpu logs detailed errors through the `log` crate, but will panic
+    // with a vague message if `env_logger::init()` isn't called.
+    //
+    // Without this, errors can fail silently and be nearly impossible to debug.
+    // ─────────────────────────────────────────────────────────────
+    env_logger::init();
+
+    let window = WindowBuilder::new()
+        .with_title("receba")
+        .with_app_id("EstamosAquiDaSilva.org")
+        .with_client_side_decorations(true)
+        .build()?;
+
+    window.run(&mut ClearApp {
+        color: wgpu::Color::BLUE,
+    })
+}
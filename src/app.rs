@@ -0,0 +1,197 @@
+use crate::input::InputEvent;
+use crate::presentation::FrameFeedback;
+use crate::shell_surface::WindowId;
+use crate::state::WgpuState;
+use crate::window::{Window, WindowSize, WindowState};
+
+// ─────────────────────────────────────────────────────────────
+// Application Callbacks
+//
+// `Window::run` owns the event loop and calls into an `App` whenever
+// something relevant happens:
+//
+//   1. `init` once, before the first event is dispatched.
+//   2. `resize` / `state_changed` / `input` / `close_requested` (and the
+//      `popup_*` callbacks for popups) as Wayland events come in.
+//   3. `update` then `render` once per compositor frame, and
+//      `frame_presented` once that frame was shown.
+//
+// Every callback except `render` gets the `Window`, so the app can
+// query its state or issue requests (e.g, closing it).
+//
+// With several windows open, `update` and `render` run once per window
+// and every callback is about a single one of them: `Window::id` (or
+// `RenderContext::window_id`) tells which, and the `Window` methods act
+// on that window.
+// ─────────────────────────────────────────────────────────────
+pub trait App {
+    /// Called once before the event loop starts. Create pipelines and buffers here.
+    fn init(&mut self, window: &mut Window) {
+        let _ = window;
+    }
+
+    /// The surface was reconfigured to a new size.
+    fn resize(&mut self, window: &mut Window, size: WindowSize) {
+        let _ = (window, size);
+    }
+
+    /// The window moved to an output with a different scale.
+    ///
+    /// Sizes reported elsewhere stay logical, only the number of pixels
+    /// behind them changes (see [`RenderContext::size`]).
+    fn scale_changed(&mut self, window: &mut Window, scale: f64) {
+        let _ = (window, scale);
+    }
+
+    /// The compositor changed the window's states (e.g, it got maximized or lost focus).
+    ///
+    /// Sent before the matching [`App::resize`], if the size changed along with it.
+    fn state_changed(&mut self, window: &mut Window, state: WindowState) {
+        let _ = (window, state);
+    }
+
+    /// Called once per frame, right before [`App::render`].
+    fn update(&mut self, window: &mut Window) {
+        let _ = window;
+    }
+
+    /// Records the commands for the current frame.
+    ///
+    /// The encoder is submitted and the frame presented once this returns.
+    fn render(&mut self, context: &mut RenderContext);
+
+    fn input(&mut self, window: &mut Window, event: InputEvent) {
+        let _ = (window, event);
+    }
+
+    /// The compositor asked the window to close.
+    ///
+    /// Returning `false` keeps it open.
+    fn close_requested(&mut self, window: &mut Window) -> bool {
+        let _ = window;
+        true
+    }
+
+    /// The popup was placed somewhere else, at (`x`, `y`) relative to its
+    /// parent's content area.
+    ///
+    /// Follows [`Window::reposition_popup`], or a move of the parent for
+    /// reactive popups.
+    fn popup_repositioned(&mut self, window: &mut Window, x: i32, y: i32) {
+        let _ = (window, x, y);
+    }
+
+    /// The compositor closed the window for good (e.g, the output of a
+    /// layer surface was unplugged). Unlike [`App::close_requested`], this
+    /// can't be refused: the window is closed once this returns.
+    fn closed(&mut self, window: &mut Window) {
+        let _ = window;
+    }
+
+    /// The compositor dismissed the popup (e.g, the user clicked outside of it).
+    ///
+    /// The popup is closed once this returns.
+    fn popup_done(&mut self, window: &mut Window) {
+        let _ = window;
+    }
+
+    /// A frame drawn earlier was shown on screen, or discarded.
+    ///
+    /// Only called when the compositor implements `wp_presentation`.
+    /// Timings of the last frames are summed up by [`Window::frame_stats`].
+    fn frame_presented(&mut self, window: &mut Window, feedback: FrameFeedback) {
+        let _ = (window, feedback);
+    }
+}
+
+/// Everything needed to draw a single frame.
+pub struct RenderContext<'a> {
+    pub(crate) wgpu_state: &'a WgpuState,
+    pub(crate) window_id: WindowId,
+    pub(crate) format: wgpu::TextureFormat,
+    pub(crate) view: wgpu::TextureView,
+    pub(crate) encoder: wgpu::CommandEncoder,
+    pub(crate) size: WindowSize,
+    pub(crate) offset: (i32, i32),
+    pub(crate) scale: f64,
+}
+
+impl RenderContext<'_> {
+    pub fn device(&self) -> &wgpu::Device {
+        self.wgpu_state.device()
+    }
+
+    pub fn queue(&self) -> &wgpu::Queue {
+        self.wgpu_state.queue()
+    }
+
+    /// The window this frame is drawn for.
+    pub fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+
+    /// Texture format of the window's surface.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// View of the swapchain texture acquired for this frame.
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    /// Size of the content area, in physical pixels.
+    ///
+    /// This is [`Window::size`] times [`RenderContext::scale`].
+    pub fn size(&self) -> WindowSize {
+        self.size
+    }
+
+    /// Scale the frame is drawn at, from logical sizes to physical pixels.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Content area within the frame, as `(x, y, width, height)` in physical pixels.
+    ///
+    /// With client-side decorations, the frame also holds the title bar and
+    /// borders, which are drawn over it once [`App::render`] returns. Pass this
+    /// to `RenderPass::set_viewport` to keep drawing out of them.
+    pub fn viewport(&self) -> (f32, f32, f32, f32) {
+        (
+            self.offset.0 as f32,
+            self.offset.1 as f32,
+            self.size.width as f32,
+            self.size.height as f32,
+        )
+    }
+
+    pub fn encoder(&mut self) -> &mut wgpu::CommandEncoder {
+        &mut self.encoder
+    }
+
+    /// The encoder along with the frame's view, to begin a render pass
+    /// targeting the frame without cloning the view.
+    pub fn encoder_and_view(&mut self) -> (&mut wgpu::CommandEncoder, &wgpu::TextureView) {
+        (&mut self.encoder, &self.view)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Window Events
+//
+// `Dispatch` impls only have access to `AppState`, not the `App`.
+// They queue what happened here and `Window::run` forwards it to
+// the `App` once the dispatch returns.
+// ─────────────────────────────────────────────────────────────
+pub(crate) enum WindowEvent {
+    Resized(WindowSize),
+    ScaleChanged(f64),
+    StateChanged(WindowState),
+    Input(InputEvent),
+    CloseRequested,
+    PopupRepositioned(i32, i32),
+    PopupDone,
+    Closed,
+    FramePresented(FrameFeedback),
+}
@@ -0,0 +1,708 @@
+use crate::app::RenderContext;
+use crate::input::{ButtonState, InputEvent, PointerEvent, TouchEvent, TouchPhase};
+use crate::shell_surface::ShellSurface;
+use crate::window::{ResizeEdge, Window, WindowSize, WindowState, WmCapabilities};
+
+const TITLE_BAR_HEIGHT: i32 = 32;
+const BORDER_WIDTH: i32 = 4;
+const BUTTON_WIDTH: i32 = 44;
+/// Corner zones are wider than the border itself so diagonal resizing is easy to hit.
+const CORNER_SIZE: i32 = 16;
+
+/// Left mouse button, as reported by `wl_pointer.button`.
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+
+const MAX_QUADS: usize = 16;
+/// `rect` (4) + `color` (4) + `kind` (1), all `f32`.
+const QUAD_SIZE: usize = 9 * std::mem::size_of::<f32>();
+
+const TITLE_BAR_COLOR: [f32; 4] = [0.09, 0.09, 0.1, 1.0];
+const BUTTON_HOVER_COLOR: [f32; 4] = [0.2, 0.2, 0.22, 1.0];
+const CLOSE_HOVER_COLOR: [f32; 4] = [0.7, 0.1, 0.1, 1.0];
+const GLYPH_COLOR: [f32; 4] = [0.9, 0.9, 0.9, 1.0];
+
+/// Space taken by the decorations on each side of the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Insets {
+    pub(crate) top: i32,
+    pub(crate) left: i32,
+    pub(crate) right: i32,
+    pub(crate) bottom: i32,
+}
+
+impl Insets {
+    pub(crate) fn shrink(&self, size: WindowSize) -> WindowSize {
+        WindowSize {
+            width: (size.width - self.left - self.right).max(1),
+            height: (size.height - self.top - self.bottom).max(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    TitleBar,
+    Minimize,
+    Maximize,
+    Close,
+    Edge(ResizeEdge),
+}
+
+/// Everything the decorations' geometry depends on.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Layout {
+    size: WindowSize,
+    state: WindowState,
+    capabilities: WmCapabilities,
+}
+
+impl Layout {
+    pub(crate) fn new(toplevel: &ShellSurface) -> Self {
+        Self {
+            size: toplevel.size.unwrap_or(toplevel.initial_size),
+            state: toplevel.window_state,
+            capabilities: toplevel.wm_capabilities,
+        }
+    }
+
+    fn insets(&self) -> Insets {
+        ClientDecorations::insets(self.state)
+    }
+
+    /// Buttons the compositor will honor, from right to left.
+    fn buttons(&self) -> impl Iterator<Item = Part> {
+        [
+            (Part::Close, true),
+            (Part::Maximize, self.capabilities.maximize),
+            (Part::Minimize, self.capabilities.minimize),
+        ]
+        .into_iter()
+        .filter_map(|(part, supported)| supported.then_some(part))
+    }
+
+    fn button_rect(&self, index: usize) -> [i32; 4] {
+        let insets = self.insets();
+        let right = self.size.width - insets.right - index as i32 * BUTTON_WIDTH;
+        let top = insets.top - TITLE_BAR_HEIGHT;
+
+        [right - BUTTON_WIDTH, top, right, insets.top]
+    }
+
+    fn hit_test(&self, x: f64, y: f64) -> Option<Part> {
+        let (x, y) = (x as i32, y as i32);
+        let size = self.size;
+        let insets = self.insets();
+
+        if insets.left > 0 {
+            let left = x < BORDER_WIDTH;
+            let right = x >= size.width - BORDER_WIDTH;
+            let top = y < BORDER_WIDTH;
+            let bottom = y >= size.height - BORDER_WIDTH;
+            let near_left = x < CORNER_SIZE;
+            let near_right = x >= size.width - CORNER_SIZE;
+            let near_top = y < CORNER_SIZE;
+            let near_bottom = y >= size.height - CORNER_SIZE;
+
+            let edge = match () {
+                _ if (top && near_left) || (left && near_top) => Some(ResizeEdge::TopLeft),
+                _ if (top && near_right) || (right && near_top) => Some(ResizeEdge::TopRight),
+                _ if (bottom && near_left) || (left && near_bottom) => Some(ResizeEdge::BottomLeft),
+                _ if (bottom && near_right) || (right && near_bottom) => {
+                    Some(ResizeEdge::BottomRight)
+                }
+                _ if top => Some(ResizeEdge::Top),
+                _ if bottom => Some(ResizeEdge::Bottom),
+                _ if left => Some(ResizeEdge::Left),
+                _ if right => Some(ResizeEdge::Right),
+                _ => None,
+            };
+
+            if let Some(edge) = edge {
+                return Some(Part::Edge(edge));
+            }
+        }
+
+        if y >= insets.top {
+            return None;
+        }
+
+        // Same rectangles the buttons are drawn in.
+        let button = self.buttons().enumerate().find_map(|(index, part)| {
+            let [left, top, right, bottom] = self.button_rect(index);
+            (x >= left && x < right && y >= top && y < bottom).then_some(part)
+        });
+        Some(button.unwrap_or(Part::TitleBar))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Client-Side Decorations
+//
+// When the compositor does not draw decorations (e.g, GNOME never grants
+// server-side ones), a window is just a borderless rectangle. This draws
+// a minimal frame instead:
+//
+//   - a title bar that moves the window, with the window menu on right click
+//   - minimize / maximize / close buttons
+//   - a thin border that resizes the window
+//
+// Everything lives inside the configured `WindowSize`: the app's content
+// area is what is left once the decorations are taken out, and the app's
+// pointer coordinates are relative to that area.
+//
+// Decorations are drawn on top of the app's frame in a second render
+// pass, into the same swapchain texture. No text rendering is done, so
+// the title bar carries no title.
+// ─────────────────────────────────────────────────────────────
+pub(crate) struct ClientDecorations {
+    pipeline: Option<wgpu::RenderPipeline>,
+    quads: Option<wgpu::Buffer>,
+    pointer: Option<(f64, f64)>,
+    /// Serial of the pointer's `enter`, reused for the synthetic `Enter` /
+    /// `Leave` sent when it crosses into the decorations and back.
+    enter_serial: u32,
+    /// Whether the app was last told the pointer is in the content area.
+    pointer_in_content: bool,
+    /// Touch points that went down on the decorations, kept from the app.
+    touches: Vec<i32>,
+}
+
+impl ClientDecorations {
+    pub(crate) fn new() -> Self {
+        Self {
+            pipeline: None,
+            quads: None,
+            pointer: None,
+            enter_serial: 0,
+            pointer_in_content: false,
+            touches: Vec::new(),
+        }
+    }
+
+    pub(crate) fn insets(state: WindowState) -> Insets {
+        // Maximized and tiled windows can't be resized freely, so they have no border.
+        let border = if state.maximized || state.is_tiled() {
+            0
+        } else {
+            BORDER_WIDTH
+        };
+
+        Insets {
+            top: TITLE_BAR_HEIGHT + border,
+            left: border,
+            right: border,
+            bottom: border,
+        }
+    }
+
+    fn pipeline(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        if self.pipeline.is_some() {
+            return;
+        }
+
+        let shader = device.create_shader_module(wgpu::include_wgsl!("csd.wgsl"));
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("csd_pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: QUAD_SIZE as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x4,
+                        1 => Float32x4,
+                        2 => Float32,
+                    ],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        self.quads = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("csd_quads"),
+            size: (MAX_QUADS * QUAD_SIZE) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.pipeline = Some(pipeline);
+    }
+
+    /// Draws the decorations over the frame the app just rendered.
+    pub(crate) fn render(&mut self, context: &mut RenderContext, layout: Layout) {
+        self.pipeline(context.device(), context.format());
+        let (Some(pipeline), Some(quads)) = (self.pipeline.as_ref(), self.quads.as_ref()) else {
+            return;
+        };
+
+        let size = layout.size;
+        let insets = layout.insets();
+        let hovered = self.pointer.and_then(|(x, y)| layout.hit_test(x, y));
+
+        // Pixel rect → clip space, `y` pointing up.
+        let to_clip = |[x0, y0, x1, y1]: [i32; 4]| {
+            let w = size.width as f32;
+            let h = size.height as f32;
+            [
+                x0 as f32 / w * 2.0 - 1.0,
+                1.0 - y0 as f32 / h * 2.0,
+                x1 as f32 / w * 2.0 - 1.0,
+                1.0 - y1 as f32 / h * 2.0,
+            ]
+        };
+
+        let mut data: Vec<f32> = Vec::with_capacity(MAX_QUADS * 9);
+        let mut push = |rect: [i32; 4], color: [f32; 4], kind: f32| {
+            data.extend_from_slice(&to_clip(rect));
+            data.extend_from_slice(&color);
+            data.push(kind);
+        };
+
+        // Title bar, then the border around the content.
+        push([0, 0, size.width, insets.top], TITLE_BAR_COLOR, 0.0);
+        push(
+            [0, insets.top, insets.left, size.height],
+            TITLE_BAR_COLOR,
+            0.0,
+        );
+        push(
+            [
+                size.width - insets.right,
+                insets.top,
+                size.width,
+                size.height,
+            ],
+            TITLE_BAR_COLOR,
+            0.0,
+        );
+        push(
+            [0, size.height - insets.bottom, size.width, size.height],
+            TITLE_BAR_COLOR,
+            0.0,
+        );
+
+        for (index, part) in layout.buttons().enumerate() {
+            let rect = layout.button_rect(index);
+            let glyph = match part {
+                Part::Close => 1.0,
+                Part::Maximize => 2.0,
+                _ => 3.0,
+            };
+
+            if hovered == Some(part) {
+                let color = if part == Part::Close {
+                    CLOSE_HOVER_COLOR
+                } else {
+                    BUTTON_HOVER_COLOR
+                };
+                push(rect, color, 0.0);
+            }
+
+            // Glyphs are drawn in a square centered on the button.
+            let side = rect[3] - rect[1];
+            let left = rect[0] + (BUTTON_WIDTH - side) / 2;
+            push([left, rect[1], left + side, rect[3]], GLYPH_COLOR, glyph);
+        }
+
+        let bytes: Vec<u8> = data.iter().flat_map(|value| value.to_ne_bytes()).collect();
+        context.queue().write_buffer(quads, 0, &bytes);
+
+        let mut pass = context
+            .encoder
+            .begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("csd_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &context.view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+        pass.set_pipeline(pipeline);
+        pass.set_vertex_buffer(0, quads.slice(..));
+        pass.draw(0..6, 0..(bytes.len() / QUAD_SIZE) as u32);
+    }
+}
+
+/// What the window loop should do with an input event after the decorations saw it.
+pub(crate) enum DecorationResponse {
+    /// The event belongs to the content area, in content-local coordinates.
+    Forward(InputEvent),
+    /// The decorations handled the event.
+    Consumed,
+    /// The close button was pressed.
+    CloseRequested,
+}
+
+impl Window {
+    // ─────────────────────────────────────────────────────────────
+    // Decoration Input
+    //
+    // Presses on the decorations are turned into `xdg_toplevel` requests:
+    //
+    //   - title bar    → `move` (left button), `show_window_menu` (right button)
+    //   - border       → `resize` from the matching edge
+    //   - buttons      → `set_minimized`, `set_maximized` / `unset_maximized`, close
+    //
+    // Everything else is forwarded to the app, shifted into content coordinates.
+    // Pointer motion and touch points over the decorations are not: to the
+    // app, the pointer leaves when it crosses into them.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn handle_decoration_input(&mut self, event: InputEvent) -> DecorationResponse {
+        let Some(toplevel) = self
+            .shell_surface()
+            .filter(|toplevel| toplevel.has_client_decorations())
+        else {
+            return DecorationResponse::Forward(event);
+        };
+
+        let layout = Layout::new(toplevel);
+        let insets = layout.insets();
+        let hit_test = |x, y| layout.hit_test(x, y);
+        let to_content = |x: f64, y: f64| (x - insets.left as f64, y - insets.top as f64);
+
+        match event {
+            InputEvent::Pointer(event) => {
+                let Some(toplevel) = self.state.shell_surface_mut(self.current) else {
+                    return DecorationResponse::Forward(InputEvent::Pointer(event));
+                };
+                let Some(decorations) = toplevel.decorations.as_mut() else {
+                    return DecorationResponse::Forward(InputEvent::Pointer(event));
+                };
+                let hovered = decorations.pointer.and_then(|(x, y)| hit_test(x, y));
+
+                match event {
+                    PointerEvent::Enter { serial, x, y } => {
+                        decorations.pointer = Some((x, y));
+                        decorations.enter_serial = serial;
+                    }
+                    PointerEvent::Motion { x, y, .. } => decorations.pointer = Some((x, y)),
+                    PointerEvent::Leave { .. } => decorations.pointer = None,
+                    _ => {}
+                }
+
+                let pointer = decorations.pointer;
+                let part = pointer.and_then(|(x, y)| hit_test(x, y));
+
+                // Button highlights follow the pointer.
+                if part != hovered {
+                    toplevel.needs_redraw = true;
+                }
+
+                // The app only sees the pointer while it is over the content
+                // area: crossing into the decorations is a `Leave` for it, and
+                // coming back an `Enter`.
+                let was_in_content = decorations.pointer_in_content;
+                decorations.pointer_in_content = pointer.is_some() && part.is_none();
+                let enter_serial = decorations.enter_serial;
+
+                match (event, part) {
+                    (PointerEvent::Enter { serial, x, y }, None) => {
+                        let (x, y) = to_content(x, y);
+                        DecorationResponse::Forward(InputEvent::Pointer(PointerEvent::Enter {
+                            serial,
+                            x,
+                            y,
+                        }))
+                    }
+                    (PointerEvent::Motion { time, x, y }, None) => {
+                        let (x, y) = to_content(x, y);
+                        let event = if was_in_content {
+                            PointerEvent::Motion { time, x, y }
+                        } else {
+                            PointerEvent::Enter {
+                                serial: enter_serial,
+                                x,
+                                y,
+                            }
+                        };
+                        DecorationResponse::Forward(InputEvent::Pointer(event))
+                    }
+                    (PointerEvent::Motion { .. }, Some(_)) | (PointerEvent::Leave { .. }, _)
+                        if was_in_content =>
+                    {
+                        DecorationResponse::Forward(InputEvent::Pointer(PointerEvent::Leave {
+                            serial: enter_serial,
+                        }))
+                    }
+                    (
+                        PointerEvent::Enter { .. }
+                        | PointerEvent::Motion { .. }
+                        | PointerEvent::Leave { .. },
+                        _,
+                    ) => DecorationResponse::Consumed,
+                    (
+                        PointerEvent::Button {
+                            serial,
+                            button,
+                            state,
+                            ..
+                        },
+                        Some(part),
+                    ) => self.press_decoration(part, serial, button, state, pointer),
+                    (PointerEvent::Axis(_), Some(_)) => DecorationResponse::Consumed,
+                    (event, _) => DecorationResponse::Forward(InputEvent::Pointer(event)),
+                }
+            }
+            InputEvent::Touch(TouchEvent::Frame(mut frame)) => {
+                let Some(decorations) = self
+                    .state
+                    .shell_surface_mut(self.current)
+                    .and_then(|toplevel| toplevel.decorations.as_mut())
+                else {
+                    return DecorationResponse::Forward(InputEvent::Touch(TouchEvent::Frame(
+                        frame,
+                    )));
+                };
+
+                // A finger landing on the decorations is kept from the app
+                // until it is lifted. On the title bar, it moves the window.
+                let mut move_serial = None;
+                frame.points.retain(|point| {
+                    let claimed = decorations.touches.contains(&point.id);
+                    match point.phase {
+                        TouchPhase::Down { serial } => match hit_test(point.x, point.y) {
+                            Some(part) => {
+                                if matches!(part, Part::TitleBar) {
+                                    move_serial = Some(serial);
+                                }
+                                decorations.touches.push(point.id);
+                                false
+                            }
+                            None => true,
+                        },
+                        TouchPhase::Moved => !claimed,
+                        TouchPhase::Up { .. } => {
+                            decorations.touches.retain(|id| *id != point.id);
+                            !claimed
+                        }
+                    }
+                });
+
+                if let Some(serial) = move_serial {
+                    self.start_move(serial);
+                }
+
+                for point in &mut frame.points {
+                    (point.x, point.y) = to_content(point.x, point.y);
+                }
+
+                if frame.points.is_empty() {
+                    DecorationResponse::Consumed
+                } else {
+                    DecorationResponse::Forward(InputEvent::Touch(TouchEvent::Frame(frame)))
+                }
+            }
+            InputEvent::Touch(TouchEvent::Cancel) => {
+                if let Some(decorations) = self
+                    .state
+                    .shell_surface_mut(self.current)
+                    .and_then(|toplevel| toplevel.decorations.as_mut())
+                {
+                    decorations.touches.clear();
+                }
+                DecorationResponse::Forward(InputEvent::Touch(TouchEvent::Cancel))
+            }
+            event => DecorationResponse::Forward(event),
+        }
+    }
+
+    fn press_decoration(
+        &mut self,
+        part: Part,
+        serial: u32,
+        button: u32,
+        state: ButtonState,
+        pointer: Option<(f64, f64)>,
+    ) -> DecorationResponse {
+        // Moves and resizes must start on press, while the compositor
+        // still considers the button held. Buttons act on release.
+        match (part, button, state) {
+            (Part::TitleBar, BTN_LEFT, ButtonState::Pressed) => self.start_move(serial),
+            (Part::TitleBar, BTN_RIGHT, ButtonState::Pressed) => {
+                if self.wm_capabilities().window_menu
+                    && let Some((x, y)) = pointer
+                {
+                    self.show_window_menu(serial, x as i32, y as i32);
+                }
+            }
+            (Part::Edge(edge), BTN_LEFT, ButtonState::Pressed) => self.start_resize(serial, edge),
+            (Part::Minimize, BTN_LEFT, ButtonState::Released) => self.set_minimized(),
+            (Part::Maximize, BTN_LEFT, ButtonState::Released) => {
+                // The state flips once the compositor confirms it in a configure.
+                if self.state().maximized {
+                    self.unset_maximized();
+                } else {
+                    self.set_maximized();
+                }
+            }
+            (Part::Close, BTN_LEFT, ButtonState::Released) => {
+                return DecorationResponse::CloseRequested;
+            }
+            _ => {}
+        }
+
+        DecorationResponse::Consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(state: WindowState, capabilities: WmCapabilities) -> Layout {
+        Layout {
+            size: WindowSize {
+                width: 400,
+                height: 300,
+            },
+            state,
+            capabilities,
+        }
+    }
+
+    fn all_capabilities() -> WmCapabilities {
+        WmCapabilities {
+            window_menu: true,
+            maximize: true,
+            fullscreen: true,
+            minimize: true,
+        }
+    }
+
+    #[test]
+    fn hit_test_corners() {
+        let layout = layout(WindowState::default(), all_capabilities());
+        let edge = |x, y| layout.hit_test(x, y);
+
+        assert_eq!(edge(0.0, 0.0), Some(Part::Edge(ResizeEdge::TopLeft)));
+        // Corner zones reach further along the border than its width.
+        assert_eq!(edge(15.0, 2.0), Some(Part::Edge(ResizeEdge::TopLeft)));
+        assert_eq!(edge(2.0, 15.0), Some(Part::Edge(ResizeEdge::TopLeft)));
+        assert_eq!(edge(16.0, 2.0), Some(Part::Edge(ResizeEdge::Top)));
+        assert_eq!(edge(399.0, 0.0), Some(Part::Edge(ResizeEdge::TopRight)));
+        assert_eq!(edge(0.0, 299.0), Some(Part::Edge(ResizeEdge::BottomLeft)));
+        assert_eq!(
+            edge(399.0, 299.0),
+            Some(Part::Edge(ResizeEdge::BottomRight))
+        );
+    }
+
+    #[test]
+    fn hit_test_edges() {
+        let layout = layout(WindowState::default(), all_capabilities());
+        let edge = |x, y| layout.hit_test(x, y);
+
+        assert_eq!(edge(200.0, 0.0), Some(Part::Edge(ResizeEdge::Top)));
+        assert_eq!(edge(200.0, 299.0), Some(Part::Edge(ResizeEdge::Bottom)));
+        assert_eq!(edge(0.0, 150.0), Some(Part::Edge(ResizeEdge::Left)));
+        assert_eq!(edge(399.0, 150.0), Some(Part::Edge(ResizeEdge::Right)));
+    }
+
+    #[test]
+    fn hit_test_inset_boundaries() {
+        let layout = layout(WindowState::default(), all_capabilities());
+        let insets = layout.insets();
+        assert_eq!(
+            insets,
+            Insets {
+                top: 36,
+                left: 4,
+                right: 4,
+                bottom: 4,
+            }
+        );
+
+        assert_eq!(
+            layout.hit_test(3.0, 150.0),
+            Some(Part::Edge(ResizeEdge::Left))
+        );
+        assert_eq!(layout.hit_test(4.0, 150.0), None);
+        assert_eq!(layout.hit_test(395.0, 150.0), None);
+        assert_eq!(
+            layout.hit_test(396.0, 150.0),
+            Some(Part::Edge(ResizeEdge::Right))
+        );
+        assert_eq!(layout.hit_test(100.0, 35.0), Some(Part::TitleBar));
+        assert_eq!(layout.hit_test(100.0, 36.0), None);
+        assert_eq!(layout.hit_test(100.0, 295.0), None);
+        assert_eq!(
+            layout.hit_test(100.0, 296.0),
+            Some(Part::Edge(ResizeEdge::Bottom))
+        );
+    }
+
+    #[test]
+    fn hit_test_buttons_match_their_rectangles() {
+        let layout = layout(WindowState::default(), all_capabilities());
+
+        assert_eq!(layout.button_rect(0), [352, 4, 396, 36]);
+        assert_eq!(layout.button_rect(1), [308, 4, 352, 36]);
+        assert_eq!(layout.button_rect(2), [264, 4, 308, 36]);
+
+        for (index, part) in [Part::Close, Part::Maximize, Part::Minimize]
+            .into_iter()
+            .enumerate()
+        {
+            let [left, top, right, bottom] = layout.button_rect(index);
+            assert_eq!(layout.hit_test(left as f64, top as f64), Some(part));
+            assert_eq!(
+                layout.hit_test((right - 1) as f64, (bottom - 1) as f64),
+                Some(part)
+            );
+        }
+        assert_eq!(layout.hit_test(263.0, 20.0), Some(Part::TitleBar));
+    }
+
+    #[test]
+    fn hit_test_skips_unsupported_buttons() {
+        let capabilities = WmCapabilities {
+            maximize: false,
+            ..all_capabilities()
+        };
+        let layout = layout(WindowState::default(), capabilities);
+
+        assert_eq!(layout.hit_test(360.0, 20.0), Some(Part::Close));
+        assert_eq!(layout.hit_test(320.0, 20.0), Some(Part::Minimize));
+        assert_eq!(layout.hit_test(280.0, 20.0), Some(Part::TitleBar));
+    }
+
+    #[test]
+    fn hit_test_maximized_has_no_border() {
+        let state = WindowState {
+            maximized: true,
+            ..WindowState::default()
+        };
+        let layout = layout(state, all_capabilities());
+
+        assert_eq!(layout.hit_test(0.0, 0.0), Some(Part::TitleBar));
+        assert_eq!(layout.hit_test(399.0, 0.0), Some(Part::Close));
+        assert_eq!(layout.hit_test(0.0, 150.0), None);
+        assert_eq!(layout.button_rect(0), [356, 0, 400, 32]);
+    }
+}
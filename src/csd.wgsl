@@ -0,0 +1,66 @@
+// Client-side decorations.
+//
+// Every element (title bar, borders, button backgrounds, button glyphs)
+// is an instanced quad. The rect is already in clip space, `kind` picks
+// the shape drawn inside it:
+//
+//   0: filled rect
+//   1: close glyph (X)
+//   2: maximize glyph (square outline)
+//   3: minimize glyph (horizontal bar)
+
+struct Quad {
+    @location(0) rect: vec4<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) kind: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) kind: u32,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32, quad: Quad) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(0.0, 1.0),
+        vec2(0.0, 1.0), vec2(1.0, 0.0), vec2(1.0, 1.0),
+    );
+    let uv = corners[index];
+
+    var out: VertexOutput;
+    out.position = vec4(mix(quad.rect.xy, quad.rect.zw, uv), 0.0, 1.0);
+    out.uv = uv;
+    out.color = quad.color;
+    out.kind = u32(quad.kind);
+    return out;
+}
+
+const STROKE: f32 = 0.05;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.uv - vec2(0.5);
+    var coverage = 1.0;
+
+    switch in.kind {
+        case 1u: {
+            let inside = max(abs(p.x), abs(p.y)) < 0.2;
+            let d = min(abs(p.x - p.y), abs(p.x + p.y)) * 0.7071;
+            coverage = select(0.0, 1.0, inside && d < STROKE * 0.5);
+        }
+        case 2u: {
+            let d = max(abs(p.x), abs(p.y));
+            coverage = select(0.0, 1.0, d < 0.2 && d > 0.2 - STROKE);
+        }
+        case 3u: {
+            let inside = abs(p.x) < 0.2 && abs(p.y - 0.1) < STROKE * 0.5;
+            coverage = select(0.0, 1.0, inside);
+        }
+        default: {}
+    }
+
+    return vec4(in.color.rgb, in.color.a * coverage);
+}
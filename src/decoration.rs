@@ -0,0 +1,86 @@
+use wayland_client::{Connection, Dispatch, QueueHandle, WEnum, delegate_noop};
+use wayland_protocols::xdg::decoration::zv1::client::{
+    zxdg_decoration_manager_v1::ZxdgDecorationManagerV1,
+    zxdg_toplevel_decoration_v1::{self, ZxdgToplevelDecorationV1},
+};
+use wayland_protocols::xdg::shell::client::xdg_toplevel;
+
+use crate::shell_surface::WindowId;
+use crate::state::AppState;
+
+/// Who draws the window decorations (title bar, borders, buttons).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecorationMode {
+    /// The app draws its own decorations, or none at all.
+    #[default]
+    ClientSide,
+    /// The compositor draws the decorations around the window.
+    ServerSide,
+}
+
+impl From<DecorationMode> for zxdg_toplevel_decoration_v1::Mode {
+    fn from(mode: DecorationMode) -> Self {
+        match mode {
+            DecorationMode::ClientSide => Self::ClientSide,
+            DecorationMode::ServerSide => Self::ServerSide,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// `zxdg_decoration_manager_v1`
+//
+// By default, `xdg_toplevel`s are expected to decorate themselves.
+// Compositors that can draw decorations on behalf of clients (KDE,
+// wlroots-based ones) advertise this global so the two can negotiate.
+//
+// Negotiation is per toplevel:
+//
+//   1. `get_toplevel_decoration()` before the toplevel's first commit.
+//   2. `set_mode()` to state a preference.
+//   3. The compositor answers with a `configure` carrying the mode it picked,
+//      which is applied along with the next `xdg_surface.configure`.
+//
+// The compositor has the final word: a preference may be ignored.
+// Without the global (e.g, on GNOME), decorations are always client-side.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn init_decoration(
+        &self,
+        xdg_toplevel: &xdg_toplevel::XdgToplevel,
+        preferred: DecorationMode,
+        id: WindowId,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Option<ZxdgToplevelDecorationV1> {
+        let manager = self.decoration_manager.as_ref()?;
+
+        let decoration = manager.get_toplevel_decoration(xdg_toplevel, queue_handle, id);
+        decoration.set_mode(preferred.into());
+
+        Some(decoration)
+    }
+}
+
+impl Dispatch<ZxdgToplevelDecorationV1, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &ZxdgToplevelDecorationV1,
+        event: zxdg_toplevel_decoration_v1::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        if let zxdg_toplevel_decoration_v1::Event::Configure { mode } = event
+            && let Some(toplevel) = state.shell_surface_mut(*id)
+        {
+            toplevel.pending_decoration_mode = Some(match mode {
+                WEnum::Value(zxdg_toplevel_decoration_v1::Mode::ServerSide) => {
+                    DecorationMode::ServerSide
+                }
+                _ => DecorationMode::ClientSide,
+            });
+        }
+    }
+}
+
+delegate_noop!(AppState: ignore ZxdgDecorationManagerV1);
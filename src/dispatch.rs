@@ -0,0 +1,528 @@
+use wayland_client::{
+    Connection, Dispatch, QueueHandle, delegate_noop,
+    protocol::{wl_callback, wl_compositor, wl_output, wl_registry, wl_seat, wl_subcompositor},
+};
+use wayland_protocols::wp::fractional_scale::v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1;
+use wayland_protocols::wp::presentation_time::client::wp_presentation::WpPresentation;
+use wayland_protocols::wp::tearing_control::v1::client::wp_tearing_control_manager_v1::WpTearingControlManagerV1;
+use wayland_protocols::wp::viewporter::client::wp_viewporter::WpViewporter;
+use wayland_protocols::xdg::decoration::zv1::client::zxdg_decoration_manager_v1::ZxdgDecorationManagerV1;
+use wayland_protocols::xdg::shell::client::{
+    xdg_surface,
+    xdg_toplevel::{self, XdgToplevel},
+    xdg_wm_base,
+};
+
+use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_shell_v1::ZwlrLayerShellV1;
+
+use crate::app::WindowEvent;
+use crate::input::{SEAT_VERSION, SeatState};
+use crate::layer_shell::LAYER_SHELL_VERSION;
+use crate::output::{OUTPUT_VERSION, Output};
+use crate::shell_surface::{Role, WindowId};
+use crate::state::{AppState, BoundGlobal};
+use crate::window::{WindowSize, WindowState, WmCapabilities};
+
+/// Highest `wl_compositor` version this crate knows how to handle.
+/// Version 6 adds `wl_surface.preferred_buffer_scale`.
+const COMPOSITOR_VERSION: u32 = 6;
+
+/// Highest `xdg_wm_base` version this crate knows how to handle.
+/// Version 7 adds the `constrained_*` toplevel states, not tracked yet.
+const WM_BASE_VERSION: u32 = 6;
+
+// ─────────────────────────────────────────────────────────────
+// Version Negotiation
+//
+// A global is advertised with the highest version the compositor
+// implements, but binding it at that version means promising to
+// handle every event and request up to it: a newer compositor could
+// otherwise send events this crate doesn't know about.
+//
+// Each global is bound at the lowest of the advertised version and
+// the highest one supported here. Requests added in between are only
+// sent after checking `Proxy::version`.
+// ─────────────────────────────────────────────────────────────
+fn supported_version(interface: &str) -> Option<u32> {
+    let version = match interface {
+        "wl_compositor" => COMPOSITOR_VERSION,
+        "wl_subcompositor" => 1,
+        "xdg_wm_base" => WM_BASE_VERSION,
+        "zxdg_decoration_manager_v1" => 1,
+        "zwlr_layer_shell_v1" => LAYER_SHELL_VERSION,
+        "wp_fractional_scale_manager_v1" => 1,
+        "wp_viewporter" => 1,
+        "wp_presentation" => 2,
+        "wp_tearing_control_manager_v1" => 1,
+        "wl_seat" => SEAT_VERSION,
+        "wl_output" => OUTPUT_VERSION,
+        _ => return None,
+    };
+
+    Some(version)
+}
+
+// ─────────────────────────────────────────────────────────────
+// Registry Binding (Wayland)
+//
+// The registry exposes all global objects (protocols/interfaces)
+// that the compositor supports (e.g, `wl_compositor`, `wl_shm`).
+//
+// When each global is advertised, we bind the ones we need.
+// Binding gives us a client-side handle to use those globals.
+//
+// Each bound interface needs an associated `Dispatch<O, _>` impl
+// to handle its incoming events.
+//
+// `O` being the Wayland object that needs event processing
+// (e.g, `wl_surface`, `xdg_wm_base`).
+// ─────────────────────────────────────────────────────────────
+impl Dispatch<wl_registry::WlRegistry, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _: &(),
+        _: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        match event {
+            wl_registry::Event::Global {
+                name,
+                interface,
+                version,
+            } => {
+                let Some(supported) = supported_version(&interface) else {
+                    return;
+                };
+                let version = version.min(supported);
+
+                match &interface[..] {
+                    "wl_compositor" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wl_compositor`
+                        //
+                        // The compositor is responsible for creating the displayable
+                        // output of multiple surfaces.
+                        //
+                        // It exposes functions to create `wl_surface`s and `wl_region`s
+                        // ─────────────────────────────────────────────────────────────
+                        let compositor = registry.bind::<wl_compositor::WlCompositor, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.compositor = Some(compositor);
+                    }
+                    "wl_subcompositor" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wl_subcompositor`
+                        //
+                        // Turns `wl_surface`s into subsurfaces of another surface.
+                        // Only needed for `Window::open_subsurface`.
+                        // ─────────────────────────────────────────────────────────────
+                        let subcompositor = registry
+                            .bind::<wl_subcompositor::WlSubcompositor, _, _>(
+                                name,
+                                version,
+                                queue_handle,
+                                (),
+                            );
+
+                        state.subcompositor = Some(subcompositor);
+                    }
+                    "xdg_wm_base" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `xdg_wm_base`
+                        //
+                        // Entry point for desktop-style windows and their features (drag, resize, maximize, etc.).
+                        //
+                        // It allows a `wl_surface` to become an `xdg_surface` and take on
+                        // a role such as `xdg_toplevel` (main window) or `xdg_popup` (popup menu).
+                        //
+                        // Required for normal windows in desktop environments.
+                        // ─────────────────────────────────────────────────────────────
+                        let wm_base = registry.bind::<xdg_wm_base::XdgWmBase, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.wm_base = Some(wm_base);
+                    }
+                    "zxdg_decoration_manager_v1" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `zxdg_decoration_manager_v1`
+                        //
+                        // Optional. Lets us ask the compositor to draw the title bar
+                        // and borders instead of the client.
+                        // ─────────────────────────────────────────────────────────────
+                        let manager = registry.bind::<ZxdgDecorationManagerV1, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.decoration_manager = Some(manager);
+                    }
+                    "zwlr_layer_shell_v1" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `zwlr_layer_shell_v1`
+                        //
+                        // Optional. Creates layer surfaces, for panels, overlays and
+                        // wallpapers. Implemented by wlroots-based compositors and
+                        // KDE, but not GNOME.
+                        // ─────────────────────────────────────────────────────────────
+                        let layer_shell = registry.bind::<ZwlrLayerShellV1, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.layer_shell = Some(layer_shell);
+                    }
+                    "wp_fractional_scale_manager_v1" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wp_fractional_scale_manager_v1` / `wp_viewporter`
+                        //
+                        // Optional, and only used together. They let windows render at
+                        // the output's exact scale (e.g, 1.5), see `scale.rs`.
+                        // ─────────────────────────────────────────────────────────────
+                        let manager = registry.bind::<WpFractionalScaleManagerV1, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.fractional_scale_manager = Some(manager);
+                    }
+                    "wp_viewporter" => {
+                        let viewporter =
+                            registry.bind::<WpViewporter, _, _>(name, version, queue_handle, ());
+
+                        state.viewporter = Some(viewporter);
+                    }
+                    "wp_presentation" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wp_presentation`
+                        //
+                        // Optional. Reports when each frame was actually shown,
+                        // see `presentation.rs`.
+                        // ─────────────────────────────────────────────────────────────
+                        let presentation =
+                            registry.bind::<WpPresentation, _, _>(name, version, queue_handle, ());
+
+                        state.presentation = Some(presentation);
+                    }
+                    "wp_tearing_control_manager_v1" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wp_tearing_control_manager_v1`
+                        //
+                        // Optional. Lets windows opt into tearing for lower latency,
+                        // see `tearing.rs`.
+                        // ─────────────────────────────────────────────────────────────
+                        let manager = registry.bind::<WpTearingControlManagerV1, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.tearing_control_manager = Some(manager);
+                    }
+                    "wl_seat" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wl_seat`
+                        //
+                        // Input devices. There may be several seats (rare outside
+                        // of multi-user setups), each one is tracked on its own.
+                        // ─────────────────────────────────────────────────────────────
+                        let seat =
+                            registry.bind::<wl_seat::WlSeat, _, _>(name, version, queue_handle, ());
+
+                        state.seats.push(SeatState::new(seat, name));
+                    }
+                    "wl_output" => {
+                        // ─────────────────────────────────────────────────────────────
+                        // `wl_output`
+                        //
+                        // One per display. Needed to pick the one a window goes
+                        // fullscreen on, and to know the scale of the ones it is on.
+                        // ─────────────────────────────────────────────────────────────
+                        let wl_output = registry.bind::<wl_output::WlOutput, _, _>(
+                            name,
+                            version,
+                            queue_handle,
+                            (),
+                        );
+
+                        state.outputs.push(Output::new(wl_output, name));
+                    }
+                    _ => {}
+                }
+
+                state.globals.push(BoundGlobal { name, interface });
+            }
+            wl_registry::Event::GlobalRemove { name } => state.remove_global(name),
+            _ => {}
+        }
+    }
+}
+
+impl AppState {
+    // ─────────────────────────────────────────────────────────────
+    // Removed Globals
+    //
+    // Globals can disappear at any time (e.g, a monitor unplugged, a seat
+    // removed). The compositor sends `global_remove` with the name the
+    // global was advertised under, which is matched against what we bound.
+    //
+    // Outputs and seats are dropped along with their state. For the
+    // other globals, existing objects keep working where the compositor
+    // allows it, but nothing new is created from them.
+    // ─────────────────────────────────────────────────────────────
+    fn remove_global(&mut self, name: u32) {
+        let Some(index) = self.globals.iter().position(|global| global.name == name) else {
+            return;
+        };
+        let global = self.globals.remove(index);
+
+        match &global.interface[..] {
+            "wl_output" => self.remove_output(name),
+            "wl_seat" => self.remove_seat(name),
+            "wl_compositor" => self.compositor = None,
+            "wl_subcompositor" => self.subcompositor = None,
+            "xdg_wm_base" => self.wm_base = None,
+            "zxdg_decoration_manager_v1" => self.decoration_manager = None,
+            "zwlr_layer_shell_v1" => self.layer_shell = None,
+            "wp_fractional_scale_manager_v1" => self.fractional_scale_manager = None,
+            "wp_viewporter" => self.viewporter = None,
+            "wp_presentation" => self.presentation = None,
+            "wp_tearing_control_manager_v1" => self.tearing_control_manager = None,
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<xdg_wm_base::XdgWmBase, ()> for AppState {
+    fn event(
+        _: &mut Self,
+        wm_base: &xdg_wm_base::XdgWmBase,
+        event: xdg_wm_base::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        if let xdg_wm_base::Event::Ping { serial } = event {
+            wm_base.pong(serial);
+        }
+    }
+}
+
+impl Dispatch<xdg_surface::XdgSurface, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        surface_xdg: &xdg_surface::XdgSurface,
+        event: xdg_surface::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        if let xdg_surface::Event::Configure { serial } = event {
+            surface_xdg.ack_configure(serial);
+            state.apply_configure(*id);
+        }
+    }
+}
+
+impl AppState {
+    // ─────────────────────────────────────────────────────────────
+    // Applying a Configure
+    //
+    // Role-specific events (`xdg_toplevel.configure`, `xdg_popup.configure`)
+    // only stage their state. It is applied all at once when the configure
+    // sequence ends (`xdg_surface.configure`, or the layer surface's own
+    // `configure`), right after acknowledging it.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn apply_configure(&mut self, id: WindowId) {
+        let (Some(shell_surface), Some(wgpu_state)) = (
+            self.surfaces
+                .iter_mut()
+                .find(|shell_surface| shell_surface.id == id),
+            self.wgpu_state.as_ref(),
+        ) else {
+            return;
+        };
+        let mut events = Vec::new();
+
+        // The content area shrinks or grows with the surface, but also when
+        // client-side decorations come and go, or lose their border.
+        let content_size = shell_surface.content_size();
+        let insets = shell_surface.content_insets();
+
+        if let Some(size) = shell_surface.pending_resize.take()
+            && shell_surface.size != Some(size)
+        {
+            shell_surface.configure_wgpu(wgpu_state.device(), size.width, size.height);
+            shell_surface.size = Some(size);
+        }
+
+        if let Some(mode) = shell_surface.pending_decoration_mode.take() {
+            shell_surface.decoration_mode = mode;
+        }
+
+        if let Some(window_state) = shell_surface.pending_window_state.take()
+            && shell_surface.window_state != window_state
+        {
+            shell_surface.window_state = window_state;
+            events.push(WindowEvent::StateChanged(window_state));
+        }
+
+        if let Some(capabilities) = shell_surface.pending_wm_capabilities.take() {
+            shell_surface.wm_capabilities = capabilities;
+        }
+
+        // A popup moved by `reposition` or, for reactive popups, by the
+        // parent moving. Its first placement isn't reported.
+        let configured = shell_surface.configured;
+        if let Role::Popup {
+            position,
+            pending_position,
+            repositioned,
+            ..
+        } = &mut shell_surface.role
+            && let Some(new_position) = pending_position.take()
+        {
+            if configured && (std::mem::take(repositioned) || *position != new_position) {
+                events.push(WindowEvent::PopupRepositioned(
+                    new_position.0,
+                    new_position.1,
+                ));
+            }
+            *position = new_position;
+        }
+
+        if let Some(size) = shell_surface.content_size()
+            && content_size != Some(size)
+        {
+            events.push(WindowEvent::Resized(size));
+        }
+
+        let insets_changed = shell_surface.content_insets() != insets;
+
+        shell_surface.configured = true;
+        shell_surface.needs_redraw = true;
+
+        for event in events {
+            self.push_event(id, event);
+        }
+
+        if insets_changed {
+            self.reposition_subsurfaces(id);
+        }
+    }
+}
+
+impl Dispatch<xdg_toplevel::XdgToplevel, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &XdgToplevel,
+        event: xdg_toplevel::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        if let xdg_toplevel::Event::Close = event {
+            state.push_event(*id, WindowEvent::CloseRequested);
+            return;
+        }
+
+        let Some(toplevel) = state.shell_surface_mut(*id) else {
+            return;
+        };
+
+        match event {
+            xdg_toplevel::Event::Configure {
+                width,
+                height,
+                states,
+            } => {
+                toplevel.pending_window_state = Some(WindowState::from_wire(&states));
+
+                // ─────────────────────────────────────────────────────────────
+                // Resize Behavior
+                //
+                // If the compositor sends a width/height of 0,
+                // it means the client is free to pick that dimension itself.
+                //
+                // This often happens on initial configuration or during certain resizes.
+                // We fall back to the size requested through the `WindowBuilder`,
+                // fitted to the size constraints and the compositor's bounds.
+                // ─────────────────────────────────────────────────────────────
+                let preferred = toplevel.preferred_size();
+                toplevel.pending_resize = Some(WindowSize {
+                    width: if width == 0 { preferred.width } else { width },
+                    height: if height == 0 {
+                        preferred.height
+                    } else {
+                        height
+                    },
+                });
+            }
+            xdg_toplevel::Event::WmCapabilities { capabilities } => {
+                toplevel.pending_wm_capabilities = Some(WmCapabilities::from_wire(&capabilities));
+            }
+            xdg_toplevel::Event::ConfigureBounds { width, height } => {
+                // ─────────────────────────────────────────────────────────────
+                // Bounds
+                //
+                // The largest size the window should pick for itself (e.g, the
+                // output minus panels). Only a hint for sizes we choose, never a
+                // constraint on sizes the compositor sends. `0x0` means unknown.
+                // ─────────────────────────────────────────────────────────────
+                toplevel.bounds = (width > 0 && height > 0).then_some(WindowSize { width, height });
+            }
+            _ => {}
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// `wl_callback`
+//
+// Fired once by the compositor for each `wl_surface.frame` request.
+// `done` means "now is a good time to draw", so the next frame is scheduled.
+// ─────────────────────────────────────────────────────────────
+impl Dispatch<wl_callback::WlCallback, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &wl_callback::WlCallback,
+        event: wl_callback::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        if let wl_callback::Event::Done { .. } = event
+            && let Some(shell_surface) = state.shell_surface_mut(*id)
+        {
+            shell_surface.frame_pending = false;
+            shell_surface.needs_redraw = true;
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Ignored Protocols
+//
+// `wl_compositor` has no events we need, but still needs a `Dispatch`
+// impl. (`wl_surface` events are handled in `scale.rs`.)
+//
+// We delegate them to `noop`, satisfying the Dispatch requirement.
+// ─────────────────────────────────────────────────────────────
+delegate_noop!(AppState: ignore wl_compositor::WlCompositor);
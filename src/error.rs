@@ -0,0 +1,102 @@
+use std::{error::Error, fmt};
+
+use wayland_client::{ConnectError, DispatchError};
+
+/// Errors returned while setting up or driving a [`Window`](crate::Window).
+#[derive(Debug)]
+pub enum WindowError {
+    /// Could not connect to a Wayland compositor (e.g, `WAYLAND_DISPLAY` is unset).
+    Connect(ConnectError),
+    /// Reading or dispatching events on the Wayland connection failed.
+    Dispatch(DispatchError),
+    /// A global the window cannot work without was never advertised, or
+    /// has been removed since.
+    MissingGlobal(&'static str),
+    /// The window a popup or subsurface was opened on has been closed, or
+    /// can't hold popups (subsurfaces can't).
+    InvalidParent,
+    /// A raw Wayland pointer handed to `wgpu` was null.
+    NullHandle(&'static str),
+    /// `wgpu` could not create a surface for the `wl_surface`.
+    CreateSurface(wgpu::CreateSurfaceError),
+    /// The adapter can't present to the surface: it supports no format for it.
+    UnsupportedSurface,
+    /// No adapter compatible with the surface was found.
+    RequestAdapter(wgpu::RequestAdapterError),
+    /// The adapter refused to hand out a device.
+    RequestDevice(wgpu::RequestDeviceError),
+    /// The next swapchain texture could not be acquired.
+    AcquireSurface(wgpu::SurfaceError),
+}
+
+impl fmt::Display for WindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect(_) => write!(f, "could not connect to a Wayland compositor"),
+            Self::Dispatch(_) => write!(f, "failed to dispatch Wayland events"),
+            Self::MissingGlobal(interface) => {
+                write!(f, "the compositor does not advertise `{interface}`")
+            }
+            Self::InvalidParent => write!(f, "the parent window is closed or can't hold popups"),
+            Self::NullHandle(handle) => write!(f, "pointer to `{handle}` is null"),
+            Self::CreateSurface(_) => write!(f, "failed to create a wgpu surface"),
+            Self::UnsupportedSurface => write!(f, "the adapter can't present to the surface"),
+            Self::RequestAdapter(_) => write!(f, "failed to request a wgpu adapter"),
+            Self::RequestDevice(_) => write!(f, "failed to request a wgpu device"),
+            Self::AcquireSurface(_) => write!(f, "failed to acquire the next swapchain texture"),
+        }
+    }
+}
+
+impl Error for WindowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Connect(error) => Some(error),
+            Self::Dispatch(error) => Some(error),
+            Self::MissingGlobal(_)
+            | Self::InvalidParent
+            | Self::NullHandle(_)
+            | Self::UnsupportedSurface => None,
+            Self::CreateSurface(error) => Some(error),
+            Self::RequestAdapter(error) => Some(error),
+            Self::RequestDevice(error) => Some(error),
+            Self::AcquireSurface(error) => Some(error),
+        }
+    }
+}
+
+impl From<ConnectError> for WindowError {
+    fn from(error: ConnectError) -> Self {
+        Self::Connect(error)
+    }
+}
+
+impl From<DispatchError> for WindowError {
+    fn from(error: DispatchError) -> Self {
+        Self::Dispatch(error)
+    }
+}
+
+impl From<wgpu::CreateSurfaceError> for WindowError {
+    fn from(error: wgpu::CreateSurfaceError) -> Self {
+        Self::CreateSurface(error)
+    }
+}
+
+impl From<wgpu::RequestAdapterError> for WindowError {
+    fn from(error: wgpu::RequestAdapterError) -> Self {
+        Self::RequestAdapter(error)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for WindowError {
+    fn from(error: wgpu::RequestDeviceError) -> Self {
+        Self::RequestDevice(error)
+    }
+}
+
+impl From<wgpu::SurfaceError> for WindowError {
+    fn from(error: wgpu::SurfaceError) -> Self {
+        Self::AcquireSurface(error)
+    }
+}
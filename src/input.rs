@@ -0,0 +1,19 @@
+mod keyboard;
+mod pointer;
+mod seat;
+mod touch;
+
+pub use keyboard::{KeyEvent, KeyState, KeyboardEvent, Keysym, Modifiers};
+pub use pointer::{AxisFrame, AxisSource, AxisValue, ButtonState, PointerEvent};
+pub use touch::{TouchEvent, TouchFrame, TouchPhase, TouchPoint};
+
+pub(crate) use seat::{SEAT_VERSION, SeatState};
+
+/// Input delivered to [`App::input`](crate::App::input).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InputEvent {
+    Pointer(PointerEvent),
+    Keyboard(KeyboardEvent),
+    Touch(TouchEvent),
+}
@@ -0,0 +1,432 @@
+use std::time::{Duration, Instant};
+
+use wayland_client::{
+    Connection, Dispatch, QueueHandle, WEnum,
+    protocol::wl_keyboard::{self, KeymapFormat},
+};
+use xkbcommon::xkb;
+
+use crate::app::WindowEvent;
+use crate::input::InputEvent;
+use crate::shell_surface::WindowId;
+use crate::state::AppState;
+
+pub use xkbcommon::xkb::Keysym;
+
+/// A keyboard event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyboardEvent {
+    /// The window gained keyboard focus.
+    Enter {
+        serial: u32,
+    },
+    /// The window lost keyboard focus. Any held key is implicitly released.
+    Leave {
+        serial: u32,
+    },
+    Key(KeyEvent),
+    /// The active modifiers changed.
+    Modifiers(Modifiers),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    pub time: u32,
+    /// Linux input event code of the physical key (e.g, `KEY_A` = `30`).
+    pub raw_code: u32,
+    /// Symbol produced by the key with the current layout and modifiers.
+    pub keysym: Keysym,
+    /// Text produced by the key, if any (e.g, `"A"` with Shift held).
+    pub text: Option<String>,
+    pub state: KeyState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    Pressed,
+    Released,
+    /// Synthesized by the client-side key repeat while the key is held.
+    Repeated,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+}
+
+// ─────────────────────────────────────────────────────────────
+// Key Repeat
+//
+// Wayland does not repeat keys: the compositor only reports presses
+// and releases. It does tell the client how repeat should behave via
+// `repeat_info`:
+//
+//   - `delay`: milliseconds a key must be held before repeating starts
+//   - `rate`: repeats per second after that (0 disables repeating)
+//
+// The event loop asks for the next deadline to know how long it may
+// block waiting for Wayland events.
+// ─────────────────────────────────────────────────────────────
+#[derive(Debug)]
+struct RepeatInfo {
+    rate: u32,
+    delay: Duration,
+}
+
+impl Default for RepeatInfo {
+    fn default() -> Self {
+        Self {
+            rate: 25,
+            delay: Duration::from_millis(600),
+        }
+    }
+}
+
+impl RepeatInfo {
+    /// Time between two repeats, `None` when repeating is disabled.
+    fn interval(&self) -> Option<Duration> {
+        // Rates above 1GHz would round the interval down to zero.
+        (self.rate != 0).then(|| (Duration::from_secs(1) / self.rate).max(Duration::from_nanos(1)))
+    }
+}
+
+#[derive(Debug)]
+struct HeldKey {
+    key: xkb::Keycode,
+    event: KeyEvent,
+    deadline: Instant,
+}
+
+impl HeldKey {
+    fn new(key: xkb::Keycode, event: KeyEvent, repeat_info: &RepeatInfo, now: Instant) -> Self {
+        Self {
+            key,
+            event,
+            deadline: now + repeat_info.delay,
+        }
+    }
+
+    /// Emits the repeat that is due by `now`, if any.
+    ///
+    /// At most one repeat is emitted per call: if the loop stalled past
+    /// several deadlines, the missed ones are dropped rather than replayed
+    /// all at once.
+    fn repeat(&mut self, repeat_info: &RepeatInfo, now: Instant) -> Option<KeyEvent> {
+        let interval = repeat_info.interval()?;
+        if self.deadline > now {
+            return None;
+        }
+
+        self.deadline += interval;
+        if self.deadline <= now {
+            self.deadline = now + interval;
+        }
+
+        Some(KeyEvent {
+            state: KeyState::Repeated,
+            ..self.event.clone()
+        })
+    }
+}
+
+pub(crate) struct KeyboardState {
+    pub(crate) keyboard: wl_keyboard::WlKeyboard,
+    /// Window with keyboard focus, between `enter` and `leave`.
+    focus: Option<WindowId>,
+    context: xkb::Context,
+    xkb_state: Option<xkb::State>,
+    repeat_info: RepeatInfo,
+    held_key: Option<HeldKey>,
+}
+
+impl KeyboardState {
+    pub(crate) fn new(keyboard: wl_keyboard::WlKeyboard) -> Self {
+        Self {
+            keyboard,
+            focus: None,
+            context: xkb::Context::new(xkb::CONTEXT_NO_FLAGS),
+            xkb_state: None,
+            repeat_info: RepeatInfo::default(),
+            held_key: None,
+        }
+    }
+
+    /// `None` when no key is held, or repeating was disabled while one was.
+    pub(crate) fn next_repeat(&self) -> Option<Instant> {
+        self.repeat_info.interval()?;
+        self.held_key.as_ref().map(|held| held.deadline)
+    }
+
+    pub(crate) fn repeat(&mut self, now: Instant) -> Option<KeyEvent> {
+        self.held_key.as_mut()?.repeat(&self.repeat_info, now)
+    }
+
+    fn modifiers(&self) -> Modifiers {
+        let Some(xkb_state) = self.xkb_state.as_ref() else {
+            return Modifiers::default();
+        };
+        let active = |name| xkb_state.mod_name_is_active(name, xkb::STATE_MODS_EFFECTIVE);
+
+        Modifiers {
+            shift: active(xkb::MOD_NAME_SHIFT),
+            ctrl: active(xkb::MOD_NAME_CTRL),
+            alt: active(xkb::MOD_NAME_ALT),
+            logo: active(xkb::MOD_NAME_LOGO),
+            caps_lock: active(xkb::MOD_NAME_CAPS),
+            num_lock: active(xkb::MOD_NAME_NUM),
+        }
+    }
+
+    fn key_event(&mut self, time: u32, raw_code: u32, pressed: bool) -> Option<KeyEvent> {
+        let xkb_state = self.xkb_state.as_ref()?;
+
+        // `wl_keyboard` reports evdev scancodes, XKB keycodes are offset by 8.
+        let key = xkb::Keycode::new(raw_code + 8);
+        let text = xkb_state.key_get_utf8(key);
+
+        let event = KeyEvent {
+            time,
+            raw_code,
+            keysym: xkb_state.key_get_one_sym(key),
+            text: (!text.is_empty()).then_some(text),
+            state: if pressed {
+                KeyState::Pressed
+            } else {
+                KeyState::Released
+            },
+        };
+
+        if pressed && self.repeat_info.rate != 0 && xkb_state.get_keymap().key_repeats(key) {
+            self.held_key = Some(HeldKey::new(
+                key,
+                event.clone(),
+                &self.repeat_info,
+                Instant::now(),
+            ));
+        } else if self.held_key.as_ref().is_some_and(|held| held.key == key) {
+            self.held_key = None;
+        }
+
+        Some(event)
+    }
+}
+
+impl AppState {
+    fn push_keyboard_event(&mut self, focus: Option<WindowId>, event: KeyboardEvent) {
+        if let Some(id) = focus {
+            self.push_event(id, WindowEvent::Input(InputEvent::Keyboard(event)));
+        }
+    }
+
+    /// The earliest moment a held key needs to repeat.
+    pub(crate) fn next_key_repeat(&self) -> Option<Instant> {
+        self.seats
+            .iter()
+            .filter_map(|seat| seat.keyboard.as_ref()?.next_repeat())
+            .min()
+    }
+
+    pub(crate) fn repeat_keys(&mut self, now: Instant) {
+        let repeats: Vec<(Option<WindowId>, KeyEvent)> = self
+            .seats
+            .iter_mut()
+            .filter_map(|seat| seat.keyboard.as_mut())
+            .flat_map(|keyboard| {
+                let focus = keyboard.focus;
+                keyboard
+                    .repeat(now)
+                    .into_iter()
+                    .map(move |event| (focus, event))
+            })
+            .collect();
+
+        for (focus, event) in repeats {
+            self.push_keyboard_event(focus, KeyboardEvent::Key(event));
+        }
+    }
+}
+
+impl Dispatch<wl_keyboard::WlKeyboard, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        keyboard: &wl_keyboard::WlKeyboard,
+        event: wl_keyboard::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        // Only `enter` names the surface, every other event goes to the
+        // window that has keyboard focus.
+        let entered = match &event {
+            wl_keyboard::Event::Enter { surface, .. } => state.window_of(surface),
+            _ => None,
+        };
+        let leaving = matches!(event, wl_keyboard::Event::Leave { .. });
+
+        let Some(keyboard_state) = state
+            .seats
+            .iter_mut()
+            .filter_map(|seat| seat.keyboard.as_mut())
+            .find(|state| &state.keyboard == keyboard)
+        else {
+            return;
+        };
+
+        let event = match event {
+            // ─────────────────────────────────────────────────────────────
+            // Keymap
+            //
+            // The compositor shares its keymap as a file descriptor to an
+            // XKB text description. It is memory-mapped and compiled into
+            // an `xkb::State`, which then translates keycodes into keysyms
+            // and text while tracking modifiers.
+            // ─────────────────────────────────────────────────────────────
+            wl_keyboard::Event::Keymap {
+                format: WEnum::Value(KeymapFormat::XkbV1),
+                fd,
+                size,
+            } => {
+                // SAFETY: the compositor guarantees `fd` points to a keymap of `size` bytes.
+                let keymap = unsafe {
+                    xkb::Keymap::new_from_fd(
+                        &keyboard_state.context,
+                        fd,
+                        size as usize,
+                        xkb::KEYMAP_FORMAT_TEXT_V1,
+                        xkb::KEYMAP_COMPILE_NO_FLAGS,
+                    )
+                };
+
+                keyboard_state.xkb_state =
+                    keymap.ok().flatten().map(|keymap| xkb::State::new(&keymap));
+                None
+            }
+            wl_keyboard::Event::Enter { serial, .. } => {
+                keyboard_state.focus = entered;
+                Some(KeyboardEvent::Enter { serial })
+            }
+            wl_keyboard::Event::Leave { serial, .. } => {
+                keyboard_state.held_key = None;
+                Some(KeyboardEvent::Leave { serial })
+            }
+            wl_keyboard::Event::Key {
+                time,
+                key,
+                state: key_state,
+                ..
+            } => {
+                let pressed = key_state == WEnum::Value(wl_keyboard::KeyState::Pressed);
+                keyboard_state
+                    .key_event(time, key, pressed)
+                    .map(KeyboardEvent::Key)
+            }
+            wl_keyboard::Event::Modifiers {
+                mods_depressed,
+                mods_latched,
+                mods_locked,
+                group,
+                ..
+            } => {
+                if let Some(xkb_state) = keyboard_state.xkb_state.as_mut() {
+                    xkb_state.update_mask(mods_depressed, mods_latched, mods_locked, 0, 0, group);
+                }
+
+                Some(KeyboardEvent::Modifiers(keyboard_state.modifiers()))
+            }
+            wl_keyboard::Event::RepeatInfo { rate, delay } => {
+                keyboard_state.repeat_info = RepeatInfo {
+                    rate: rate.max(0) as u32,
+                    delay: Duration::from_millis(delay.max(0) as u64),
+                };
+                None
+            }
+            _ => None,
+        };
+
+        let focus = keyboard_state.focus;
+        if leaving {
+            keyboard_state.focus = None;
+        }
+
+        if let Some(event) = event {
+            state.push_keyboard_event(focus, event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn held_key(repeat_info: &RepeatInfo, now: Instant) -> HeldKey {
+        let event = KeyEvent {
+            time: 0,
+            raw_code: 30,
+            keysym: Keysym::a,
+            text: Some("a".to_owned()),
+            state: KeyState::Pressed,
+        };
+
+        HeldKey::new(xkb::Keycode::new(38), event, repeat_info, now)
+    }
+
+    #[test]
+    fn repeat_waits_for_the_delay() {
+        let repeat_info = RepeatInfo {
+            rate: 10,
+            delay: Duration::from_millis(500),
+        };
+        let pressed = Instant::now();
+        let mut held = held_key(&repeat_info, pressed);
+
+        assert_eq!(held.repeat(&repeat_info, pressed), None);
+        assert_eq!(
+            held.repeat(&repeat_info, pressed + Duration::from_millis(499)),
+            None
+        );
+
+        let event = held
+            .repeat(&repeat_info, pressed + Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(event.state, KeyState::Repeated);
+        assert_eq!(event.keysym, Keysym::a);
+        assert_eq!(held.deadline, pressed + Duration::from_millis(600));
+    }
+
+    #[test]
+    fn repeat_disabled_at_rate_zero() {
+        let repeat_info = RepeatInfo {
+            rate: 0,
+            delay: Duration::from_millis(500),
+        };
+        let pressed = Instant::now();
+        let mut held = held_key(&repeat_info, pressed);
+
+        assert_eq!(repeat_info.interval(), None);
+        assert_eq!(
+            held.repeat(&repeat_info, pressed + Duration::from_secs(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn repeat_drops_missed_deadlines() {
+        let repeat_info = RepeatInfo {
+            rate: 10,
+            delay: Duration::from_millis(500),
+        };
+        let pressed = Instant::now();
+        let mut held = held_key(&repeat_info, pressed);
+
+        // The loop stalled for ten intervals past the delay.
+        let now = pressed + Duration::from_millis(1500);
+        assert!(held.repeat(&repeat_info, now).is_some());
+        assert_eq!(held.repeat(&repeat_info, now), None);
+        assert_eq!(held.deadline, now + Duration::from_millis(100));
+    }
+}
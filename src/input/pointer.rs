@@ -0,0 +1,406 @@
+use wayland_client::{Connection, Dispatch, Proxy, QueueHandle, WEnum, protocol::wl_pointer};
+
+use crate::app::WindowEvent;
+use crate::input::InputEvent;
+use crate::state::AppState;
+
+/// A pointer event, in surface-local coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointerEvent {
+    /// The pointer entered the window.
+    Enter {
+        serial: u32,
+        x: f64,
+        y: f64,
+    },
+    /// The pointer left the window.
+    Leave {
+        serial: u32,
+    },
+    Motion {
+        time: u32,
+        x: f64,
+        y: f64,
+    },
+    /// `button` is a Linux input event code (e.g, `BTN_LEFT` = `0x110`).
+    ///
+    /// The `serial` is what interactive requests like moving the window expect.
+    Button {
+        serial: u32,
+        time: u32,
+        button: u32,
+        state: ButtonState,
+    },
+    /// All scroll information that arrived within one `wl_pointer.frame`.
+    Axis(AxisFrame),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonState {
+    Pressed,
+    Released,
+}
+
+/// What kind of device produced a scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisSource {
+    /// A physical wheel with discrete steps.
+    Wheel,
+    /// A finger on a touchpad. Expect a `stop` once it is lifted.
+    Finger,
+    /// Continuous movement with no physical steps (e.g, button-based scrolling).
+    Continuous,
+    /// A sideways tilt of a wheel.
+    WheelTilt,
+}
+
+/// Scroll on a single axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AxisValue {
+    /// Scroll distance, in the same unit as pointer motion.
+    pub absolute: f64,
+    /// Wheel scroll in fractions of a detent, where `120` is one full step.
+    ///
+    /// Only set for wheel sources.
+    pub value120: i32,
+    /// The scroll sequence on this axis ended (e.g, fingers lifted from a touchpad).
+    pub stop: bool,
+    /// The physical direction is inverted (natural scrolling).
+    pub inverted: bool,
+}
+
+impl AxisValue {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AxisFrame {
+    pub time: u32,
+    pub source: Option<AxisSource>,
+    pub horizontal: AxisValue,
+    pub vertical: AxisValue,
+}
+
+// ─────────────────────────────────────────────────────────────
+// Pointer Frames
+//
+// Since `wl_pointer` version 5, events are grouped into logical frames
+// terminated by a `frame` event. A single scroll gesture may arrive as:
+//
+//   axis_source → axis_value120 → axis (vertical) → axis (horizontal) → frame
+//
+// Axis events are accumulated into `PendingAxis` and delivered as one
+// `AxisFrame` when the `frame` event arrives. Older pointers never send
+// `frame`, so their axis events are delivered immediately.
+// ─────────────────────────────────────────────────────────────
+#[derive(Debug, Default)]
+pub(crate) struct PendingAxis {
+    frame: AxisFrame,
+}
+
+impl PendingAxis {
+    fn axis_mut(&mut self, axis: WEnum<wl_pointer::Axis>) -> Option<&mut AxisValue> {
+        match axis {
+            WEnum::Value(wl_pointer::Axis::HorizontalScroll) => Some(&mut self.frame.horizontal),
+            WEnum::Value(wl_pointer::Axis::VerticalScroll) => Some(&mut self.frame.vertical),
+            _ => None,
+        }
+    }
+
+    /// Accumulates an axis event into the frame, other events are ignored.
+    fn push(&mut self, event: wl_pointer::Event) {
+        match event {
+            wl_pointer::Event::Axis { time, axis, value } => {
+                self.frame.time = time;
+                if let Some(axis) = self.axis_mut(axis) {
+                    axis.absolute += value;
+                }
+            }
+            wl_pointer::Event::AxisSource {
+                axis_source: WEnum::Value(source),
+            } => {
+                self.frame.source = Some(source.into());
+            }
+            wl_pointer::Event::AxisStop { time, axis } => {
+                self.frame.time = time;
+                if let Some(axis) = self.axis_mut(axis) {
+                    axis.stop = true;
+                }
+            }
+            // `axis_discrete` is replaced by `axis_value120` since version 8,
+            // both are normalized to fractions of 120.
+            wl_pointer::Event::AxisDiscrete { axis, discrete } => {
+                if let Some(axis) = self.axis_mut(axis) {
+                    axis.value120 += discrete * 120;
+                }
+            }
+            wl_pointer::Event::AxisValue120 { axis, value120 } => {
+                if let Some(axis) = self.axis_mut(axis) {
+                    axis.value120 += value120;
+                }
+            }
+            wl_pointer::Event::AxisRelativeDirection {
+                axis,
+                direction: WEnum::Value(direction),
+            } => {
+                if let Some(axis) = self.axis_mut(axis) {
+                    axis.inverted = direction == wl_pointer::AxisRelativeDirection::Inverted;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn take(&mut self) -> Option<AxisFrame> {
+        let frame = std::mem::take(&mut self.frame);
+
+        if frame.horizontal.is_empty() && frame.vertical.is_empty() {
+            None
+        } else {
+            Some(frame)
+        }
+    }
+}
+
+impl From<wl_pointer::AxisSource> for AxisSource {
+    fn from(source: wl_pointer::AxisSource) -> Self {
+        match source {
+            wl_pointer::AxisSource::Finger => Self::Finger,
+            wl_pointer::AxisSource::Continuous => Self::Continuous,
+            wl_pointer::AxisSource::WheelTilt => Self::WheelTilt,
+            _ => Self::Wheel,
+        }
+    }
+}
+
+impl AppState {
+    fn push_pointer_event(&mut self, pointer: &wl_pointer::WlPointer, event: PointerEvent) {
+        let focus = self
+            .seats
+            .iter()
+            .find(|seat| seat.pointer.as_ref() == Some(pointer))
+            .and_then(|seat| seat.pointer_focus);
+
+        if let Some(id) = focus {
+            self.push_event(id, WindowEvent::Input(InputEvent::Pointer(event)));
+        }
+    }
+}
+
+impl Dispatch<wl_pointer::WlPointer, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        pointer: &wl_pointer::WlPointer,
+        event: wl_pointer::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        match event {
+            // ─────────────────────────────────────────────────────────────
+            // Focus
+            //
+            // Only `enter` and `leave` name the surface. Everything in
+            // between goes to the window the pointer last entered.
+            // ─────────────────────────────────────────────────────────────
+            wl_pointer::Event::Enter {
+                serial,
+                surface,
+                surface_x,
+                surface_y,
+            } => {
+                let focus = state.window_of(&surface);
+                if let Some(seat) = state
+                    .seats
+                    .iter_mut()
+                    .find(|seat| seat.pointer.as_ref() == Some(pointer))
+                {
+                    seat.pointer_focus = focus;
+                }
+
+                state.push_pointer_event(
+                    pointer,
+                    PointerEvent::Enter {
+                        serial,
+                        x: surface_x,
+                        y: surface_y,
+                    },
+                );
+            }
+            wl_pointer::Event::Leave { serial, .. } => {
+                state.push_pointer_event(pointer, PointerEvent::Leave { serial });
+
+                if let Some(seat) = state
+                    .seats
+                    .iter_mut()
+                    .find(|seat| seat.pointer.as_ref() == Some(pointer))
+                {
+                    seat.pointer_focus = None;
+                }
+            }
+            wl_pointer::Event::Motion {
+                time,
+                surface_x,
+                surface_y,
+            } => state.push_pointer_event(
+                pointer,
+                PointerEvent::Motion {
+                    time,
+                    x: surface_x,
+                    y: surface_y,
+                },
+            ),
+            wl_pointer::Event::Button {
+                serial,
+                time,
+                button,
+                state: button_state,
+            } => {
+                let button_state = match button_state {
+                    WEnum::Value(wl_pointer::ButtonState::Pressed) => ButtonState::Pressed,
+                    _ => ButtonState::Released,
+                };
+
+                state.set_input_seat(|seat| seat.pointer.as_ref() == Some(pointer));
+
+                state.push_pointer_event(
+                    pointer,
+                    PointerEvent::Button {
+                        serial,
+                        time,
+                        button,
+                        state: button_state,
+                    },
+                );
+            }
+            event => {
+                let Some(seat_state) = state
+                    .seats
+                    .iter_mut()
+                    .find(|seat| seat.pointer.as_ref() == Some(pointer))
+                else {
+                    return;
+                };
+                let pending = &mut seat_state.pending_axis;
+
+                let frame_ended = matches!(event, wl_pointer::Event::Frame)
+                    || pointer.version() < wl_pointer::EVT_FRAME_SINCE;
+
+                pending.push(event);
+
+                if frame_ended && let Some(frame) = pending.take() {
+                    state.push_pointer_event(pointer, PointerEvent::Axis(frame));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wl_pointer::Axis::{HorizontalScroll, VerticalScroll};
+
+    #[test]
+    fn axis_frame_mixes_continuous_and_discrete_scroll() {
+        let mut pending = PendingAxis::default();
+
+        for event in [
+            wl_pointer::Event::AxisSource {
+                axis_source: WEnum::Value(wl_pointer::AxisSource::Wheel),
+            },
+            wl_pointer::Event::AxisValue120 {
+                axis: WEnum::Value(VerticalScroll),
+                value120: 60,
+            },
+            wl_pointer::Event::AxisValue120 {
+                axis: WEnum::Value(VerticalScroll),
+                value120: 60,
+            },
+            wl_pointer::Event::AxisDiscrete {
+                axis: WEnum::Value(HorizontalScroll),
+                discrete: -1,
+            },
+            wl_pointer::Event::AxisRelativeDirection {
+                axis: WEnum::Value(VerticalScroll),
+                direction: WEnum::Value(wl_pointer::AxisRelativeDirection::Inverted),
+            },
+            wl_pointer::Event::Axis {
+                time: 10,
+                axis: WEnum::Value(VerticalScroll),
+                value: 7.5,
+            },
+            wl_pointer::Event::Axis {
+                time: 10,
+                axis: WEnum::Value(VerticalScroll),
+                value: 2.5,
+            },
+            wl_pointer::Event::Axis {
+                time: 10,
+                axis: WEnum::Value(HorizontalScroll),
+                value: -10.0,
+            },
+            // Unknown axes are ignored.
+            wl_pointer::Event::Axis {
+                time: 10,
+                axis: WEnum::Unknown(7),
+                value: 100.0,
+            },
+        ] {
+            pending.push(event);
+        }
+
+        assert_eq!(
+            pending.take(),
+            Some(AxisFrame {
+                time: 10,
+                source: Some(AxisSource::Wheel),
+                horizontal: AxisValue {
+                    absolute: -10.0,
+                    value120: -120,
+                    stop: false,
+                    inverted: false,
+                },
+                vertical: AxisValue {
+                    absolute: 10.0,
+                    value120: 120,
+                    stop: false,
+                    inverted: true,
+                },
+            })
+        );
+        assert_eq!(pending.take(), None);
+    }
+
+    #[test]
+    fn axis_frame_with_stop_only() {
+        let mut pending = PendingAxis::default();
+
+        pending.push(wl_pointer::Event::AxisSource {
+            axis_source: WEnum::Value(wl_pointer::AxisSource::Finger),
+        });
+        pending.push(wl_pointer::Event::AxisStop {
+            time: 20,
+            axis: WEnum::Value(VerticalScroll),
+        });
+
+        let frame = pending.take().unwrap();
+        assert_eq!(frame.time, 20);
+        assert_eq!(frame.source, Some(AxisSource::Finger));
+        assert!(frame.vertical.stop);
+        assert!(frame.horizontal.is_empty());
+    }
+
+    #[test]
+    fn axis_frame_without_scroll_is_dropped() {
+        let mut pending = PendingAxis::default();
+
+        pending.push(wl_pointer::Event::AxisSource {
+            axis_source: WEnum::Value(wl_pointer::AxisSource::Wheel),
+        });
+        assert_eq!(pending.take(), None);
+    }
+}
@@ -0,0 +1,196 @@
+use wayland_client::{
+    Connection, Dispatch, Proxy, QueueHandle, WEnum,
+    protocol::{wl_pointer, wl_seat},
+};
+
+use crate::input::keyboard::KeyboardState;
+use crate::input::pointer::PendingAxis;
+use crate::input::touch::TouchState;
+use crate::shell_surface::WindowId;
+use crate::state::AppState;
+
+/// Highest `wl_seat` version whose events we understand.
+pub(crate) const SEAT_VERSION: u32 = 9;
+
+// ─────────────────────────────────────────────────────────────
+// `wl_seat`
+//
+// A seat is a group of input devices used by one person
+// (usually one keyboard, one pointer and maybe a touchscreen).
+//
+// The seat does not deliver input itself. It announces which
+// capabilities it currently has, and the client requests a
+// `wl_pointer` / `wl_keyboard` / `wl_touch` for each one it cares about.
+//
+// Capabilities change at runtime (e.g, a mouse being unplugged),
+// so the device objects are created and released as they come and go.
+// ─────────────────────────────────────────────────────────────
+pub(crate) struct SeatState {
+    pub(crate) seat: wl_seat::WlSeat,
+    /// Name of the registry global, to match its removal.
+    pub(crate) global_name: u32,
+    pub(crate) name: Option<String>,
+    pub(crate) capabilities: wl_seat::Capability,
+
+    pub(crate) pointer: Option<wl_pointer::WlPointer>,
+    /// Window the pointer is over, between `enter` and `leave`.
+    pub(crate) pointer_focus: Option<WindowId>,
+    pub(crate) pending_axis: PendingAxis,
+
+    pub(crate) keyboard: Option<KeyboardState>,
+
+    pub(crate) touch: Option<TouchState>,
+}
+
+impl SeatState {
+    pub(crate) fn new(seat: wl_seat::WlSeat, global_name: u32) -> Self {
+        Self {
+            seat,
+            global_name,
+            name: None,
+            capabilities: wl_seat::Capability::empty(),
+            pointer: None,
+            pointer_focus: None,
+            pending_axis: PendingAxis::default(),
+            keyboard: None,
+            touch: None,
+        }
+    }
+}
+
+impl AppState {
+    pub(crate) fn seat_mut(&mut self, seat: &wl_seat::WlSeat) -> Option<&mut SeatState> {
+        self.seats.iter_mut().find(|state| &state.seat == seat)
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Input Serials
+    //
+    // Interactive requests (moving, resizing, showing the window menu)
+    // must name both the seat and the serial of the input event that
+    // triggered them. The compositor ignores them otherwise.
+    //
+    // The app only ever sees serials, so we remember which seat
+    // produced the latest press.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn set_input_seat(&mut self, owns: impl Fn(&SeatState) -> bool) {
+        if let Some(seat_state) = self.seats.iter().find(|seat_state| owns(seat_state)) {
+            self.input_seat = Some(seat_state.seat.clone());
+        }
+    }
+
+    /// Forgets a removed seat, releasing its devices.
+    ///
+    /// Windows get no `leave` events for it: keys held or buttons pressed
+    /// on that seat are simply never released.
+    pub(crate) fn remove_seat(&mut self, global_name: u32) {
+        let Some(index) = self
+            .seats
+            .iter()
+            .position(|seat_state| seat_state.global_name == global_name)
+        else {
+            return;
+        };
+        let seat_state = self.seats.remove(index);
+
+        if let Some(pointer) = seat_state.pointer
+            && pointer.version() >= 3
+        {
+            pointer.release();
+        }
+        if let Some(keyboard_state) = seat_state.keyboard
+            && keyboard_state.keyboard.version() >= 3
+        {
+            keyboard_state.keyboard.release();
+        }
+        if let Some(touch_state) = seat_state.touch
+            && touch_state.touch.version() >= 3
+        {
+            touch_state.touch.release();
+        }
+        if seat_state.seat.version() >= 5 {
+            seat_state.seat.release();
+        }
+
+        if self.input_seat.as_ref() == Some(&seat_state.seat) {
+            self.input_seat = None;
+        }
+    }
+
+    /// The seat that produced the latest press, to pair with its serial.
+    pub(crate) fn input_seat(&self) -> Option<&wl_seat::WlSeat> {
+        self.input_seat
+            .as_ref()
+            .or_else(|| self.seats.first().map(|seat_state| &seat_state.seat))
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        seat: &wl_seat::WlSeat,
+        event: wl_seat::Event,
+        _: &(),
+        _: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        let Some(seat_state) = state.seat_mut(seat) else {
+            return;
+        };
+
+        match event {
+            wl_seat::Event::Capabilities {
+                capabilities: WEnum::Value(capabilities),
+            } => {
+                seat_state.capabilities = capabilities;
+
+                let has_pointer = capabilities.contains(wl_seat::Capability::Pointer);
+                match (has_pointer, seat_state.pointer.take()) {
+                    (true, None) => {
+                        seat_state.pointer = Some(seat.get_pointer(queue_handle, ()));
+                    }
+                    (false, Some(pointer)) => {
+                        // `release` only exists since version 3, older
+                        // pointers are destroyed along with the seat.
+                        if pointer.version() >= 3 {
+                            pointer.release();
+                        }
+                    }
+                    (_, pointer) => seat_state.pointer = pointer,
+                }
+
+                let has_keyboard = capabilities.contains(wl_seat::Capability::Keyboard);
+                match (has_keyboard, seat_state.keyboard.take()) {
+                    (true, None) => {
+                        let keyboard = seat.get_keyboard(queue_handle, ());
+                        seat_state.keyboard = Some(KeyboardState::new(keyboard));
+                    }
+                    (false, Some(keyboard_state)) => {
+                        if keyboard_state.keyboard.version() >= 3 {
+                            keyboard_state.keyboard.release();
+                        }
+                    }
+                    (_, keyboard) => seat_state.keyboard = keyboard,
+                }
+
+                let has_touch = capabilities.contains(wl_seat::Capability::Touch);
+                match (has_touch, seat_state.touch.take()) {
+                    (true, None) => {
+                        let touch = seat.get_touch(queue_handle, ());
+                        seat_state.touch = Some(TouchState::new(touch));
+                    }
+                    (false, Some(touch_state)) => {
+                        if touch_state.touch.version() >= 3 {
+                            touch_state.touch.release();
+                        }
+                    }
+                    (_, touch) => seat_state.touch = touch,
+                }
+            }
+            wl_seat::Event::Name { name } => {
+                seat_state.name = Some(name);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use wayland_client::{Connection, Dispatch, QueueHandle, protocol::wl_touch};
+
+use crate::app::WindowEvent;
+use crate::input::InputEvent;
+use crate::shell_surface::WindowId;
+use crate::state::AppState;
+
+/// A touch event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchEvent {
+    /// Every touch point that changed within one `wl_touch.frame`.
+    Frame(TouchFrame),
+    /// The compositor took over the touch sequence (e.g, for a gesture).
+    ///
+    /// All active touch points are gone and should be treated as aborted.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchFrame {
+    pub points: Vec<TouchPoint>,
+}
+
+/// A single finger on the touch surface, in surface-local coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchPoint {
+    /// Unique for as long as the finger stays down. May be reused afterwards.
+    pub id: i32,
+    pub phase: TouchPhase,
+    pub time: u32,
+    pub x: f64,
+    pub y: f64,
+    /// Contact ellipse axes, if the device reports them.
+    pub major: Option<f64>,
+    pub minor: Option<f64>,
+    /// Angle of the contact ellipse in degrees, if the device reports it.
+    pub orientation: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    /// The `serial` is what interactive requests like moving the window expect.
+    Down {
+        serial: u32,
+    },
+    Moved,
+    Up {
+        serial: u32,
+    },
+}
+
+// ─────────────────────────────────────────────────────────────
+// Touch Frames
+//
+// `wl_touch` reports each finger separately (`down`, `motion`, `up`,
+// `shape`, `orientation`) and closes a logical group of changes with a
+// `frame` event. Two fingers moving at once arrive as:
+//
+//   motion (id 0) → motion (id 1) → frame
+//
+// Points are tracked by id across frames, since `up` carries no
+// position. On `frame` the points that changed are delivered together,
+// and lifted fingers are forgotten.
+//
+// Each finger stays bound to the window it went down on. Fingers on
+// different windows within one frame are delivered as one frame per window.
+// ─────────────────────────────────────────────────────────────
+#[derive(Default)]
+struct TouchPoints {
+    points: Vec<TouchPoint>,
+    windows: Vec<(i32, WindowId)>,
+    changed: Vec<i32>,
+}
+
+impl TouchPoints {
+    /// A finger going down starts a new point, even if the id was used
+    /// before and somehow never lifted.
+    fn down(&mut self, window: Option<WindowId>, point: TouchPoint) {
+        let id = point.id;
+        self.points.retain(|point| point.id != id);
+        self.windows.retain(|(point_id, _)| *point_id != id);
+        if let Some(window) = window {
+            self.windows.push((id, window));
+        }
+        self.points.push(point);
+        self.point_mut(id);
+    }
+
+    fn point_mut(&mut self, id: i32) -> Option<&mut TouchPoint> {
+        if !self.changed.contains(&id) {
+            self.changed.push(id);
+        }
+
+        self.points.iter_mut().find(|point| point.id == id)
+    }
+
+    fn window(&self, id: i32) -> Option<WindowId> {
+        self.windows
+            .iter()
+            .find(|(point_id, _)| *point_id == id)
+            .map(|(_, window)| *window)
+    }
+
+    fn take_frames(&mut self) -> Vec<(WindowId, TouchFrame)> {
+        let changed = std::mem::take(&mut self.changed);
+        let mut frames: Vec<(WindowId, TouchFrame)> = Vec::new();
+
+        for point in self
+            .points
+            .iter()
+            .filter(|point| changed.contains(&point.id))
+        {
+            let Some(window) = self.window(point.id) else {
+                continue;
+            };
+
+            match frames.iter_mut().find(|(id, _)| *id == window) {
+                Some((_, frame)) => frame.points.push(point.clone()),
+                None => frames.push((
+                    window,
+                    TouchFrame {
+                        points: vec![point.clone()],
+                    },
+                )),
+            }
+        }
+
+        let lifted: Vec<i32> = self
+            .points
+            .iter()
+            .filter(|point| matches!(point.phase, TouchPhase::Up { .. }))
+            .map(|point| point.id)
+            .collect();
+        self.points.retain(|point| !lifted.contains(&point.id));
+        self.windows.retain(|(id, _)| !lifted.contains(id));
+        for point in &mut self.points {
+            point.phase = TouchPhase::Moved;
+        }
+
+        frames
+    }
+
+    /// Forgets every point, returning each window that had a finger on it once.
+    fn cancel(&mut self) -> Vec<WindowId> {
+        let mut windows: Vec<WindowId> = Vec::new();
+        for (_, window) in &self.windows {
+            if !windows.contains(window) {
+                windows.push(*window);
+            }
+        }
+
+        *self = Self::default();
+        windows
+    }
+}
+
+pub(crate) struct TouchState {
+    pub(crate) touch: wl_touch::WlTouch,
+    points: TouchPoints,
+}
+
+impl TouchState {
+    pub(crate) fn new(touch: wl_touch::WlTouch) -> Self {
+        Self {
+            touch,
+            points: TouchPoints::default(),
+        }
+    }
+}
+
+impl AppState {
+    fn push_touch_event(&mut self, id: WindowId, event: TouchEvent) {
+        self.push_event(id, WindowEvent::Input(InputEvent::Touch(event)));
+    }
+}
+
+impl Dispatch<wl_touch::WlTouch, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        touch: &wl_touch::WlTouch,
+        event: wl_touch::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        let entered = match &event {
+            wl_touch::Event::Down { surface, .. } => state.window_of(surface),
+            _ => None,
+        };
+
+        let Some(touch_state) = state
+            .seats
+            .iter_mut()
+            .filter_map(|seat| seat.touch.as_mut())
+            .find(|state| &state.touch == touch)
+        else {
+            return;
+        };
+
+        let events: Vec<(WindowId, TouchEvent)> = match event {
+            wl_touch::Event::Down {
+                serial,
+                time,
+                id,
+                x,
+                y,
+                ..
+            } => {
+                touch_state.points.down(
+                    entered,
+                    TouchPoint {
+                        id,
+                        phase: TouchPhase::Down { serial },
+                        time,
+                        x,
+                        y,
+                        major: None,
+                        minor: None,
+                        orientation: None,
+                    },
+                );
+
+                state.set_input_seat(|seat| {
+                    seat.touch
+                        .as_ref()
+                        .is_some_and(|state| &state.touch == touch)
+                });
+                Vec::new()
+            }
+            wl_touch::Event::Up { serial, time, id } => {
+                if let Some(point) = touch_state.points.point_mut(id) {
+                    point.phase = TouchPhase::Up { serial };
+                    point.time = time;
+                }
+                Vec::new()
+            }
+            wl_touch::Event::Motion { time, id, x, y } => {
+                if let Some(point) = touch_state.points.point_mut(id) {
+                    point.time = time;
+                    point.x = x;
+                    point.y = y;
+                }
+                Vec::new()
+            }
+            wl_touch::Event::Shape { id, major, minor } => {
+                if let Some(point) = touch_state.points.point_mut(id) {
+                    point.major = Some(major);
+                    point.minor = Some(minor);
+                }
+                Vec::new()
+            }
+            wl_touch::Event::Orientation { id, orientation } => {
+                if let Some(point) = touch_state.points.point_mut(id) {
+                    point.orientation = Some(orientation);
+                }
+                Vec::new()
+            }
+            wl_touch::Event::Frame => touch_state
+                .points
+                .take_frames()
+                .into_iter()
+                .map(|(window, frame)| (window, TouchEvent::Frame(frame)))
+                .collect(),
+            wl_touch::Event::Cancel => {
+                // Every window that had a finger on it loses the sequence.
+                touch_state
+                    .points
+                    .cancel()
+                    .into_iter()
+                    .map(|window| (window, TouchEvent::Cancel))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        for (window, event) in events {
+            state.push_touch_event(window, event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(id: i32, phase: TouchPhase, x: f64, y: f64) -> TouchPoint {
+        TouchPoint {
+            id,
+            phase,
+            time: 0,
+            x,
+            y,
+            major: None,
+            minor: None,
+            orientation: None,
+        }
+    }
+
+    fn motion(points: &mut TouchPoints, id: i32, x: f64, y: f64) {
+        if let Some(point) = points.point_mut(id) {
+            point.x = x;
+            point.y = y;
+        }
+    }
+
+    #[test]
+    fn frame_groups_changes_until_frame() {
+        let window = WindowId(1);
+        let mut points = TouchPoints::default();
+
+        points.down(
+            Some(window),
+            point(0, TouchPhase::Down { serial: 1 }, 1.0, 1.0),
+        );
+        points.down(
+            Some(window),
+            point(1, TouchPhase::Down { serial: 2 }, 5.0, 5.0),
+        );
+        motion(&mut points, 0, 2.0, 2.0);
+
+        let frames = points.take_frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(
+            frames[0],
+            (
+                window,
+                TouchFrame {
+                    points: vec![
+                        point(0, TouchPhase::Down { serial: 1 }, 2.0, 2.0),
+                        point(1, TouchPhase::Down { serial: 2 }, 5.0, 5.0),
+                    ],
+                }
+            )
+        );
+
+        // Only the points that changed since the last frame are delivered.
+        motion(&mut points, 1, 6.0, 6.0);
+        assert_eq!(
+            points.take_frames(),
+            vec![(
+                window,
+                TouchFrame {
+                    points: vec![point(1, TouchPhase::Moved, 6.0, 6.0)],
+                }
+            )]
+        );
+        assert_eq!(points.take_frames(), Vec::new());
+    }
+
+    #[test]
+    fn lifted_point_keeps_its_position_and_is_forgotten() {
+        let window = WindowId(1);
+        let mut points = TouchPoints::default();
+
+        points.down(
+            Some(window),
+            point(0, TouchPhase::Down { serial: 1 }, 3.0, 4.0),
+        );
+        points.take_frames();
+
+        if let Some(point) = points.point_mut(0) {
+            point.phase = TouchPhase::Up { serial: 2 };
+        }
+        assert_eq!(
+            points.take_frames(),
+            vec![(
+                window,
+                TouchFrame {
+                    points: vec![point(0, TouchPhase::Up { serial: 2 }, 3.0, 4.0)],
+                }
+            )]
+        );
+
+        assert!(points.points.is_empty());
+        assert!(points.windows.is_empty());
+        motion(&mut points, 0, 5.0, 5.0);
+        assert_eq!(points.take_frames(), Vec::new());
+    }
+
+    #[test]
+    fn frame_splits_points_per_window() {
+        let mut points = TouchPoints::default();
+
+        points.down(
+            Some(WindowId(1)),
+            point(0, TouchPhase::Down { serial: 1 }, 0.0, 0.0),
+        );
+        points.down(
+            Some(WindowId(2)),
+            point(1, TouchPhase::Down { serial: 2 }, 0.0, 0.0),
+        );
+        points.down(
+            Some(WindowId(1)),
+            point(2, TouchPhase::Down { serial: 3 }, 0.0, 0.0),
+        );
+        // Went down outside any known window.
+        points.down(None, point(3, TouchPhase::Down { serial: 4 }, 0.0, 0.0));
+
+        let frames = points.take_frames();
+        let ids = |window: WindowId| -> Vec<i32> {
+            frames
+                .iter()
+                .find(|(id, _)| *id == window)
+                .map(|(_, frame)| frame.points.iter().map(|point| point.id).collect())
+                .unwrap_or_default()
+        };
+        assert_eq!(frames.len(), 2);
+        assert_eq!(ids(WindowId(1)), vec![0, 2]);
+        assert_eq!(ids(WindowId(2)), vec![1]);
+    }
+
+    #[test]
+    fn cancel_clears_points_once_per_window() {
+        let mut points = TouchPoints::default();
+
+        points.down(
+            Some(WindowId(1)),
+            point(0, TouchPhase::Down { serial: 1 }, 0.0, 0.0),
+        );
+        points.down(
+            Some(WindowId(2)),
+            point(1, TouchPhase::Down { serial: 2 }, 0.0, 0.0),
+        );
+        points.down(
+            Some(WindowId(1)),
+            point(2, TouchPhase::Down { serial: 3 }, 0.0, 0.0),
+        );
+
+        assert_eq!(points.cancel(), vec![WindowId(1), WindowId(2)]);
+        assert_eq!(points.take_frames(), Vec::new());
+        assert!(points.points.is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+use wayland_client::{Connection, Dispatch, Proxy, QueueHandle, delegate_noop};
+use wayland_protocols_wlr::layer_shell::v1::client::{zwlr_layer_shell_v1, zwlr_layer_surface_v1};
+
+use crate::app::WindowEvent;
+use crate::error::WindowError;
+use crate::output::Output;
+use crate::shell_surface::{Role, ShellSurface, WindowId};
+use crate::state::AppState;
+use crate::surface_config::SurfaceConfig;
+use crate::window::{Window, WindowSize};
+
+/// Highest `zwlr_layer_shell_v1` version this crate knows how to handle.
+pub(crate) const LAYER_SHELL_VERSION: u32 = 4;
+
+/// Stacking layer of a layer surface, from bottom to top.
+///
+/// Regular windows are drawn between `Bottom` and `Top`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Wallpapers.
+    Background,
+    /// Desktop widgets, below windows.
+    Bottom,
+    /// Panels and docks, above windows.
+    Top,
+    /// Lock screens and notifications, above fullscreen windows too.
+    Overlay,
+}
+
+impl From<Layer> for zwlr_layer_shell_v1::Layer {
+    fn from(layer: Layer) -> Self {
+        match layer {
+            Layer::Background => Self::Background,
+            Layer::Bottom => Self::Bottom,
+            Layer::Top => Self::Top,
+            Layer::Overlay => Self::Overlay,
+        }
+    }
+}
+
+/// Output edges a layer surface is attached to.
+///
+/// With no edge set, the surface is centered. Anchoring two opposite
+/// edges stretches it between them when its size on that axis is `0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayerAnchor {
+    pub top: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl From<LayerAnchor> for zwlr_layer_surface_v1::Anchor {
+    fn from(anchor: LayerAnchor) -> Self {
+        let edges = [
+            (anchor.top, Self::Top),
+            (anchor.bottom, Self::Bottom),
+            (anchor.left, Self::Left),
+            (anchor.right, Self::Right),
+        ];
+
+        edges
+            .into_iter()
+            .filter(|(anchored, _)| *anchored)
+            .fold(Self::empty(), |edges, (_, edge)| edges | edge)
+    }
+}
+
+/// Distance kept between a layer surface and the edges it is anchored to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Margin {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+/// Whether a layer surface takes keyboard focus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyboardInteractivity {
+    /// Never gets keyboard focus (e.g, a wallpaper or a status bar).
+    #[default]
+    None,
+    /// Takes keyboard focus away from everything else while mapped, on the
+    /// `Top` and `Overlay` layers (e.g, a lock screen or a launcher).
+    Exclusive,
+    /// Gets keyboard focus like a regular window, when clicked.
+    ///
+    /// Falls back to `None` on compositors older than version 4.
+    OnDemand,
+}
+
+/// Configures and opens a layer surface: a panel, overlay or wallpaper
+/// placed by the compositor on an output instead of managed as a window.
+///
+/// Needs a compositor implementing `zwlr_layer_shell_v1` (e.g, Sway,
+/// Hyprland, KDE). It is opened through [`LayerSurfaceBuilder::build`], or next
+/// to other windows with [`Window::open_layer_surface`].
+#[derive(Debug, Clone)]
+pub struct LayerSurfaceBuilder {
+    pub(crate) namespace: String,
+    pub(crate) layer: Layer,
+    pub(crate) output: Option<Output>,
+    pub(crate) size: WindowSize,
+    pub(crate) anchor: LayerAnchor,
+    pub(crate) exclusive_zone: i32,
+    pub(crate) margin: Margin,
+    pub(crate) keyboard_interactivity: KeyboardInteractivity,
+    pub(crate) surface_config: SurfaceConfig,
+}
+
+impl Default for LayerSurfaceBuilder {
+    fn default() -> Self {
+        Self {
+            namespace: String::new(),
+            layer: Layer::Top,
+            output: None,
+            size: WindowSize::default(),
+            anchor: LayerAnchor::default(),
+            exclusive_zone: 0,
+            margin: Margin::default(),
+            keyboard_interactivity: KeyboardInteractivity::default(),
+            surface_config: SurfaceConfig::default(),
+        }
+    }
+}
+
+impl LayerSurfaceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What the surface is for (e.g, `"panel"`), so compositors can apply
+    /// rules to it. Cannot be changed later.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Layer to stack the surface on. Defaults to [`Layer::Top`].
+    pub fn with_layer(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Output to show the surface on. By default the compositor picks one,
+    /// usually the focused one.
+    pub fn with_output(mut self, output: &Output) -> Self {
+        self.output = Some(output.clone());
+        self
+    }
+
+    /// Size of the surface. A dimension of `0` stretches the surface between
+    /// the two edges it is anchored to on that axis, which both need to be set.
+    pub fn with_size(mut self, size: WindowSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: LayerAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Space reserved along the anchored edge, which other surfaces and
+    /// maximized windows keep out of (e.g, the height of a panel).
+    ///
+    /// `0` (the default) moves the surface out of the way of other exclusive
+    /// zones, `-1` ignores them and stretches over the whole output.
+    pub fn with_exclusive_zone(mut self, zone: i32) -> Self {
+        self.exclusive_zone = zone;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: Margin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_keyboard_interactivity(mut self, interactivity: KeyboardInteractivity) -> Self {
+        self.keyboard_interactivity = interactivity;
+        self
+    }
+
+    /// See [`SurfaceConfig::with_present_mode`].
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.surface_config = self.surface_config.with_present_mode(present_mode);
+        self
+    }
+
+    /// See [`SurfaceConfig`].
+    pub fn with_surface_config(mut self, surface_config: SurfaceConfig) -> Self {
+        self.surface_config = surface_config;
+        self
+    }
+
+    /// Connects to the compositor, creates the layer surface and sets up `wgpu`.
+    pub fn build(self) -> Result<Window, WindowError> {
+        Window::connect(|state, connection, queue_handle| {
+            state.open_layer_surface(self, connection, queue_handle)
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Layer Shell
+//
+// `zwlr_layer_shell_v1` gives a `wl_surface` the role of a "layer surface",
+// an alternative to `xdg_toplevel` for desktop components. Instead of
+// being managed by the user, a layer surface is placed on an output by
+// the rules it sets:
+//
+//   - the layer it is stacked on
+//   - the output edges it is anchored to, and its margins from them
+//   - the exclusive zone it reserves along its edge
+//
+// Like an `xdg_surface`, it is set up before an initial commit without a
+// buffer, and only drawn to once the compositor answered with a configure.
+// Unlike one, it has no separate role object: the configure arrives on the
+// layer surface itself.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn open_layer_surface(
+        &mut self,
+        builder: LayerSurfaceBuilder,
+        connection: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<WindowId, WindowError> {
+        let layer_shell = self
+            .layer_shell
+            .clone()
+            .ok_or(WindowError::MissingGlobal("zwlr_layer_shell_v1"))?;
+
+        let (wl_surface, surface, settings) =
+            self.create_surfaces(connection, &builder.surface_config, queue_handle)?;
+
+        let id = self.next_window_id();
+
+        let layer_surface = layer_shell.get_layer_surface(
+            &wl_surface,
+            builder.output.as_ref().map(|output| &output.wl_output),
+            builder.layer.into(),
+            builder.namespace,
+            queue_handle,
+            id,
+        );
+
+        // Double-buffered, applied by the initial commit below.
+        layer_surface.set_size(
+            builder.size.width.max(0) as u32,
+            builder.size.height.max(0) as u32,
+        );
+        layer_surface.set_anchor(builder.anchor.into());
+        layer_surface.set_exclusive_zone(builder.exclusive_zone);
+        layer_surface.set_margin(
+            builder.margin.top,
+            builder.margin.right,
+            builder.margin.bottom,
+            builder.margin.left,
+        );
+        layer_surface.set_keyboard_interactivity(match builder.keyboard_interactivity {
+            KeyboardInteractivity::Exclusive => {
+                zwlr_layer_surface_v1::KeyboardInteractivity::Exclusive
+            }
+            KeyboardInteractivity::OnDemand if layer_surface.version() >= 4 => {
+                zwlr_layer_surface_v1::KeyboardInteractivity::OnDemand
+            }
+            _ => zwlr_layer_surface_v1::KeyboardInteractivity::None,
+        });
+
+        wl_surface.commit();
+
+        let mut shell_surface = ShellSurface::new(
+            id,
+            wl_surface,
+            Role::Layer { layer_surface },
+            surface,
+            settings,
+            builder.size,
+        );
+        self.init_scaling(&mut shell_surface, queue_handle);
+        self.surfaces.push(shell_surface);
+
+        Ok(id)
+    }
+}
+
+impl Dispatch<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        layer_surface: &zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+        event: zwlr_layer_surface_v1::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        match event {
+            zwlr_layer_surface_v1::Event::Configure {
+                serial,
+                width,
+                height,
+            } => {
+                layer_surface.ack_configure(serial);
+
+                // `0` leaves the dimension to us, as with `xdg_toplevel`.
+                if let Some(shell_surface) = state.shell_surface_mut(*id) {
+                    let requested = shell_surface.initial_size;
+                    shell_surface.pending_resize = Some(WindowSize {
+                        width: if width == 0 {
+                            requested.width
+                        } else {
+                            width as i32
+                        }
+                        .max(1),
+                        height: if height == 0 {
+                            requested.height
+                        } else {
+                            height as i32
+                        }
+                        .max(1),
+                    });
+                }
+
+                state.apply_configure(*id);
+            }
+            // ─────────────────────────────────────────────────────────────
+            // `closed`
+            //
+            // The compositor will not show the surface anymore (e.g, its
+            // output was unplugged). Nothing is left to do but destroy it,
+            // which `Window::run` does after telling the app.
+            // ─────────────────────────────────────────────────────────────
+            zwlr_layer_surface_v1::Event::Closed => state.push_event(*id, WindowEvent::Closed),
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(AppState: ignore zwlr_layer_shell_v1::ZwlrLayerShellV1);
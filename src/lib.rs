@@ -0,0 +1,73 @@
+//! A minimal Wayland window with a `wgpu` surface attached to it.
+//!
+//! The crate talks to the compositor directly through `wayland-client`
+//! and `wayland-protocols`, then hands the resulting `wl_surface` to `wgpu`
+//! through `raw-window-handle`.
+//!
+//! ```no_run
+//! use wgpu_wayland_window::{App, RenderContext, WindowBuilder, WindowSize};
+//!
+//! struct Clear;
+//!
+//! impl App for Clear {
+//!     fn render(&mut self, context: &mut RenderContext) {
+//!         let (encoder, view) = context.encoder_and_view();
+//!         encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+//!             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+//!                 view,
+//!                 depth_slice: None,
+//!                 resolve_target: None,
+//!                 ops: wgpu::Operations {
+//!                     load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+//!                     store: wgpu::StoreOp::Store,
+//!                 },
+//!             })],
+//!             ..Default::default()
+//!         });
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), wgpu_wayland_window::WindowError> {
+//! WindowBuilder::new()
+//!     .with_title("example")
+//!     .with_size(WindowSize { width: 640, height: 480 })
+//!     .build()?
+//!     .run(&mut Clear)
+//! # }
+//! ```
+
+mod app;
+mod csd;
+mod decoration;
+mod dispatch;
+mod error;
+mod input;
+mod layer_shell;
+mod output;
+mod popup;
+mod presentation;
+mod render;
+mod scale;
+mod shell_surface;
+mod state;
+mod subsurface;
+mod surface_config;
+mod tearing;
+mod window;
+
+pub use app::{App, RenderContext};
+pub use decoration::DecorationMode;
+pub use error::WindowError;
+pub use input::{
+    AxisFrame, AxisSource, AxisValue, ButtonState, InputEvent, KeyEvent, KeyState, KeyboardEvent,
+    Keysym, Modifiers, PointerEvent, TouchEvent, TouchFrame, TouchPhase, TouchPoint,
+};
+pub use layer_shell::{KeyboardInteractivity, Layer, LayerAnchor, LayerSurfaceBuilder, Margin};
+pub use output::{Output, OutputInfo, OutputMode};
+pub use popup::{ConstraintAdjustment, PopupAnchor, PopupBuilder, PopupGravity};
+pub use presentation::{FrameFeedback, FrameStats, PresentationFlags, PresentedFrame};
+pub use shell_surface::WindowId;
+pub use state::WgpuState;
+pub use subsurface::SubsurfaceBuilder;
+pub use surface_config::{SurfaceConfig, SurfaceSettings};
+pub use window::{ResizeEdge, Window, WindowBuilder, WindowSize, WindowState, WmCapabilities};
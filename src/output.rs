@@ -0,0 +1,207 @@
+use wayland_client::{Connection, Dispatch, Proxy, QueueHandle, WEnum, protocol::wl_output};
+
+use crate::shell_surface::WindowId;
+use crate::state::AppState;
+
+/// Highest `wl_output` version this crate knows how to handle.
+pub(crate) const OUTPUT_VERSION: u32 = 4;
+
+/// A display advertised by the compositor.
+///
+/// Outputs come and go as monitors are plugged in, see [`Window::outputs`](crate::Window::outputs).
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub(crate) wl_output: wl_output::WlOutput,
+    /// Name of the registry global, to match its removal.
+    pub(crate) global_name: u32,
+    pub(crate) info: OutputInfo,
+    pub(crate) pending: OutputInfo,
+}
+
+impl PartialEq for Output {
+    fn eq(&self, other: &Self) -> bool {
+        self.wl_output == other.wl_output
+    }
+}
+
+impl Eq for Output {}
+
+impl Output {
+    pub(crate) fn new(wl_output: wl_output::WlOutput, global_name: u32) -> Self {
+        Self {
+            wl_output,
+            global_name,
+            info: OutputInfo::default(),
+            pending: OutputInfo::default(),
+        }
+    }
+
+    /// What the compositor last told about the output.
+    pub fn info(&self) -> &OutputInfo {
+        &self.info
+    }
+}
+
+/// Description of an [`Output`], as last announced by the compositor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputInfo {
+    /// Name given by the compositor (e.g, `"DP-1"`), stable across sessions.
+    /// Only sent since `wl_output` version 4.
+    pub name: Option<String>,
+    /// Human-readable description (e.g, `"Dell Inc. U2720Q (DP-1)"`).
+    /// Only sent since `wl_output` version 4.
+    pub description: Option<String>,
+    pub make: String,
+    pub model: String,
+    /// Position within the global compositor space, in logical pixels.
+    pub position: (i32, i32),
+    /// Physical size in millimeters, `(0, 0)` when unknown (e.g, projectors).
+    pub physical_size: (i32, i32),
+    /// Current mode, if the compositor announced one.
+    pub mode: Option<OutputMode>,
+    /// Integer scale factor. Windows on this output render at this scale
+    /// when fractional scaling isn't available.
+    pub scale: i32,
+}
+
+impl Default for OutputInfo {
+    fn default() -> Self {
+        Self {
+            name: None,
+            description: None,
+            make: String::new(),
+            model: String::new(),
+            position: (0, 0),
+            physical_size: (0, 0),
+            mode: None,
+            scale: 1,
+        }
+    }
+}
+
+/// Resolution and refresh rate of an output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputMode {
+    /// Size in physical pixels.
+    pub width: i32,
+    pub height: i32,
+    /// Refresh rate in mHz (e.g, `60000` for 60 Hz), `0` if unknown.
+    pub refresh: i32,
+}
+
+impl AppState {
+    /// Forgets an unplugged output. Windows that were on it get rescaled
+    /// for the outputs they are left on.
+    pub(crate) fn remove_output(&mut self, global_name: u32) {
+        let Some(index) = self
+            .outputs
+            .iter()
+            .position(|output| output.global_name == global_name)
+        else {
+            return;
+        };
+        let output = self.outputs.remove(index);
+
+        // The compositor normally sends `wl_surface.leave` first, but
+        // nothing guarantees it.
+        let ids: Vec<WindowId> = self
+            .surfaces
+            .iter_mut()
+            .filter(|shell_surface| shell_surface.outputs.contains(&output.wl_output))
+            .map(|shell_surface| {
+                shell_surface
+                    .outputs
+                    .retain(|entered| entered != &output.wl_output);
+                shell_surface.id
+            })
+            .collect();
+        for id in ids {
+            self.update_buffer_scale(id);
+        }
+
+        // `release` only exists since version 3.
+        if output.wl_output.version() >= 3 {
+            output.wl_output.release();
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Output Properties
+//
+// On bind, and whenever something changes, the compositor sends the
+// output's properties as a batch of events (`geometry`, `mode`, `scale`,
+// `name`, `description`) closed by `done`.
+//
+// They are staged and only applied on `done`, so a half-updated output
+// is never observed. Version 1 has no `done`: each event applies as it
+// arrives.
+//
+// A new scale affects the windows on the output, which are rescaled
+// once the batch is applied.
+// ─────────────────────────────────────────────────────────────
+impl Dispatch<wl_output::WlOutput, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        wl_output: &wl_output::WlOutput,
+        event: wl_output::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        let Some(output) = state
+            .outputs
+            .iter_mut()
+            .find(|output| &output.wl_output == wl_output)
+        else {
+            return;
+        };
+        let done = matches!(event, wl_output::Event::Done) || wl_output.version() < 2;
+        let info = &mut output.pending;
+
+        match event {
+            wl_output::Event::Geometry {
+                x,
+                y,
+                physical_width,
+                physical_height,
+                make,
+                model,
+                ..
+            } => {
+                info.position = (x, y);
+                info.physical_size = (physical_width, physical_height);
+                info.make = make;
+                info.model = model;
+            }
+            wl_output::Event::Mode {
+                flags,
+                width,
+                height,
+                refresh,
+            } => {
+                // Outputs used to list every supported mode, only the
+                // current one matters.
+                if let WEnum::Value(flags) = flags
+                    && flags.contains(wl_output::Mode::Current)
+                {
+                    info.mode = Some(OutputMode {
+                        width,
+                        height,
+                        refresh,
+                    });
+                }
+            }
+            wl_output::Event::Scale { factor } => info.scale = factor.max(1),
+            wl_output::Event::Name { name } => info.name = Some(name),
+            wl_output::Event::Description { description } => info.description = Some(description),
+            wl_output::Event::Done => {}
+            _ => return,
+        }
+
+        if done {
+            output.info = output.pending.clone();
+            state.update_buffer_scales(wl_output);
+        }
+    }
+}
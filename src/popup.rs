@@ -0,0 +1,437 @@
+use wayland_client::{Connection, Dispatch, Proxy, QueueHandle, delegate_noop};
+use wayland_protocols::xdg::shell::client::{xdg_popup, xdg_positioner};
+
+use crate::app::WindowEvent;
+use crate::error::WindowError;
+use crate::shell_surface::{Role, ShellSurface, WindowId};
+use crate::state::AppState;
+use crate::surface_config::SurfaceConfig;
+use crate::window::WindowSize;
+
+/// Point of the anchor rectangle a popup is attached to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PopupAnchor {
+    /// The center of the rectangle.
+    #[default]
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    BottomLeft,
+    TopRight,
+    BottomRight,
+}
+
+impl From<PopupAnchor> for xdg_positioner::Anchor {
+    fn from(anchor: PopupAnchor) -> Self {
+        match anchor {
+            PopupAnchor::None => Self::None,
+            PopupAnchor::Top => Self::Top,
+            PopupAnchor::Bottom => Self::Bottom,
+            PopupAnchor::Left => Self::Left,
+            PopupAnchor::Right => Self::Right,
+            PopupAnchor::TopLeft => Self::TopLeft,
+            PopupAnchor::BottomLeft => Self::BottomLeft,
+            PopupAnchor::TopRight => Self::TopRight,
+            PopupAnchor::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+/// Direction a popup extends in from its anchor point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PopupGravity {
+    /// Centered on the anchor point.
+    #[default]
+    None,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    BottomLeft,
+    TopRight,
+    BottomRight,
+}
+
+impl From<PopupGravity> for xdg_positioner::Gravity {
+    fn from(gravity: PopupGravity) -> Self {
+        match gravity {
+            PopupGravity::None => Self::None,
+            PopupGravity::Top => Self::Top,
+            PopupGravity::Bottom => Self::Bottom,
+            PopupGravity::Left => Self::Left,
+            PopupGravity::Right => Self::Right,
+            PopupGravity::TopLeft => Self::TopLeft,
+            PopupGravity::BottomLeft => Self::BottomLeft,
+            PopupGravity::TopRight => Self::TopRight,
+            PopupGravity::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+/// How the compositor may move or resize a popup that would not fit on screen.
+///
+/// Adjustments are tried in order: flip, then slide, then resize. With none
+/// enabled (the default), the popup is left partially off screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConstraintAdjustment {
+    /// Move the popup along the axis until it fits.
+    pub slide_x: bool,
+    pub slide_y: bool,
+    /// Mirror the anchor and gravity on the axis (e.g, open a menu upwards
+    /// near the bottom of the screen).
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Shrink the popup on the axis. Its new size comes with the configure.
+    pub resize_x: bool,
+    pub resize_y: bool,
+}
+
+impl From<ConstraintAdjustment> for xdg_positioner::ConstraintAdjustment {
+    fn from(adjustment: ConstraintAdjustment) -> Self {
+        let flags = [
+            (adjustment.slide_x, Self::SlideX),
+            (adjustment.slide_y, Self::SlideY),
+            (adjustment.flip_x, Self::FlipX),
+            (adjustment.flip_y, Self::FlipY),
+            (adjustment.resize_x, Self::ResizeX),
+            (adjustment.resize_y, Self::ResizeY),
+        ];
+
+        flags
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .fold(Self::None, |flags, (_, flag)| flags | flag)
+    }
+}
+
+/// Configures a popup (menu, tooltip) opened with [`Window::open_popup`](crate::Window::open_popup).
+///
+/// Positions are relative to the parent's content area, the same coordinates
+/// its input events use.
+#[derive(Debug, Clone)]
+pub struct PopupBuilder {
+    pub(crate) size: WindowSize,
+    pub(crate) anchor_rect: (i32, i32, i32, i32),
+    pub(crate) anchor: PopupAnchor,
+    pub(crate) gravity: PopupGravity,
+    pub(crate) constraint_adjustment: ConstraintAdjustment,
+    pub(crate) offset: (i32, i32),
+    pub(crate) reactive: bool,
+    pub(crate) grab: Option<u32>,
+    pub(crate) surface_config: SurfaceConfig,
+}
+
+impl PopupBuilder {
+    pub fn new(size: WindowSize) -> Self {
+        Self {
+            size,
+            anchor_rect: (0, 0, 1, 1),
+            anchor: PopupAnchor::default(),
+            gravity: PopupGravity::default(),
+            constraint_adjustment: ConstraintAdjustment::default(),
+            offset: (0, 0),
+            reactive: false,
+            grab: None,
+            surface_config: SurfaceConfig::default(),
+        }
+    }
+
+    /// Rectangle of the parent the popup is placed against (e.g, the menu
+    /// button, or a 1x1 rectangle under the pointer). Defaults to the
+    /// parent's top-left pixel.
+    pub fn with_anchor_rect(mut self, x: i32, y: i32, width: i32, height: i32) -> Self {
+        self.anchor_rect = (x, y, width.max(1), height.max(1));
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: PopupAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_gravity(mut self, gravity: PopupGravity) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_constraint_adjustment(mut self, adjustment: ConstraintAdjustment) -> Self {
+        self.constraint_adjustment = adjustment;
+        self
+    }
+
+    /// Moves the popup away from its anchor point, once it is placed.
+    pub fn with_offset(mut self, x: i32, y: i32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Asks the compositor to place the popup again whenever the parent
+    /// moves or resizes, reported through [`App::popup_repositioned`](crate::App::popup_repositioned).
+    ///
+    /// Ignored by compositors older than `xdg_wm_base` version 3.
+    pub fn with_reactive(mut self, reactive: bool) -> Self {
+        self.reactive = reactive;
+        self
+    }
+
+    /// Grabs input for the popup, in response to the input event with `serial`
+    /// (usually the button press that opened it).
+    ///
+    /// Grabbing popups get keyboard focus, and are dismissed by the
+    /// compositor when the user clicks outside of them, see
+    /// [`App::popup_done`](crate::App::popup_done).
+    pub fn with_grab(mut self, serial: u32) -> Self {
+        self.grab = Some(serial);
+        self
+    }
+
+    /// See [`SurfaceConfig::with_present_mode`].
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.surface_config = self.surface_config.with_present_mode(present_mode);
+        self
+    }
+
+    /// See [`SurfaceConfig`].
+    pub fn with_surface_config(mut self, surface_config: SurfaceConfig) -> Self {
+        self.surface_config = surface_config;
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Popups
+//
+// An `xdg_popup` is placed by the compositor, never by the client.
+// The client describes where it would like it with an `xdg_positioner`:
+//
+//   1. An anchor rectangle on the parent (e.g, the button that opened it).
+//   2. The anchor, the point of that rectangle the popup hangs from.
+//   3. The gravity, the direction the popup extends in from there.
+//   4. The constraint adjustments allowed when it doesn't fit on screen.
+//
+// The positioner is only read when the popup is created (or
+// repositioned), so it is destroyed right after.
+//
+// The resulting position and size arrive with `xdg_popup.configure`,
+// relative to the parent, and are applied at the next `xdg_surface.configure`
+// like every other configure.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn open_popup(
+        &mut self,
+        parent: WindowId,
+        builder: PopupBuilder,
+        connection: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<WindowId, WindowError> {
+        let wm_base = self
+            .wm_base
+            .clone()
+            .ok_or(WindowError::MissingGlobal("xdg_wm_base"))?;
+        let (parent_xdg_surface, parent_layer_surface) =
+            match self.shell_surface(parent).map(|parent| &parent.role) {
+                Some(Role::Toplevel { xdg_surface, .. } | Role::Popup { xdg_surface, .. }) => {
+                    (Some(xdg_surface.clone()), None)
+                }
+                Some(Role::Layer { layer_surface }) => (None, Some(layer_surface.clone())),
+                Some(Role::Subsurface { .. }) | None => return Err(WindowError::InvalidParent),
+            };
+
+        let positioner = self.create_positioner(parent, &builder, queue_handle)?;
+        let (wl_surface, surface, settings) =
+            match self.create_surfaces(connection, &builder.surface_config, queue_handle) {
+                Ok(surfaces) => surfaces,
+                Err(error) => {
+                    positioner.destroy();
+                    return Err(error);
+                }
+            };
+
+        let id = self.next_window_id();
+
+        let xdg_surface = wm_base.get_xdg_surface(&wl_surface, queue_handle, id);
+
+        // ─────────────────────────────────────────────────────────────
+        // Parents
+        //
+        // An `xdg_popup` is normally parented to another `xdg_surface`.
+        // Layer surfaces have none: the popup is created without a parent,
+        // then the layer surface adopts it before the initial commit.
+        // ─────────────────────────────────────────────────────────────
+        let xdg_popup =
+            xdg_surface.get_popup(parent_xdg_surface.as_ref(), &positioner, queue_handle, id);
+        if let Some(layer_surface) = parent_layer_surface {
+            layer_surface.get_popup(&xdg_popup);
+        }
+        positioner.destroy();
+
+        // ─────────────────────────────────────────────────────────────
+        // Grabs
+        //
+        // Must be requested before the initial commit, with the serial of
+        // a recent input event. The compositor refuses grabs it can't tie
+        // to user action by dismissing the popup right away.
+        // ─────────────────────────────────────────────────────────────
+        if let (Some(serial), Some(seat)) = (builder.grab, self.input_seat()) {
+            xdg_popup.grab(seat, serial);
+        }
+
+        wl_surface.commit();
+
+        let mut shell_surface = ShellSurface::new(
+            id,
+            wl_surface,
+            Role::Popup {
+                xdg_surface,
+                xdg_popup,
+                parent,
+                position: (0, 0),
+                pending_position: None,
+                repositioned: false,
+            },
+            surface,
+            settings,
+            builder.size,
+        );
+        self.init_scaling(&mut shell_surface, queue_handle);
+        self.surfaces.push(shell_surface);
+
+        Ok(id)
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Repositioning
+    //
+    // Since version 3, a mapped popup can be given a new positioner
+    // (e.g, a completion list following the cursor). The compositor
+    // answers with `repositioned`, then a regular configure.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn reposition_popup(
+        &mut self,
+        id: WindowId,
+        builder: &PopupBuilder,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        let Some((xdg_popup, parent)) = self.shell_surface(id).and_then(|shell_surface| {
+            Some((shell_surface.xdg_popup()?.clone(), shell_surface.parent()?))
+        }) else {
+            return;
+        };
+
+        if xdg_popup.version() < 3 {
+            return;
+        }
+
+        if let Ok(positioner) = self.create_positioner(parent, builder, queue_handle) {
+            xdg_popup.reposition(&positioner, id.0);
+            positioner.destroy();
+        }
+    }
+
+    fn create_positioner(
+        &self,
+        parent: WindowId,
+        builder: &PopupBuilder,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<xdg_positioner::XdgPositioner, WindowError> {
+        let wm_base = self
+            .wm_base
+            .as_ref()
+            .ok_or(WindowError::MissingGlobal("xdg_wm_base"))?;
+        let parent = self
+            .shell_surface(parent)
+            .ok_or(WindowError::InvalidParent)?;
+
+        // The positioner works in the parent's surface coordinates, which
+        // include the client-side decorations.
+        let insets = parent.content_insets();
+        let (x, y, width, height) = builder.anchor_rect;
+
+        let positioner = wm_base.create_positioner(queue_handle, ());
+        positioner.set_size(builder.size.width.max(1), builder.size.height.max(1));
+        positioner.set_anchor_rect(x + insets.left, y + insets.top, width, height);
+        positioner.set_anchor(builder.anchor.into());
+        positioner.set_gravity(builder.gravity.into());
+        positioner.set_constraint_adjustment(builder.constraint_adjustment.into());
+        positioner.set_offset(builder.offset.0, builder.offset.1);
+
+        if positioner.version() >= 3 {
+            if builder.reactive {
+                positioner.set_reactive();
+            }
+            if let Some(size) = parent.size {
+                positioner.set_parent_size(size.width, size.height);
+            }
+        }
+
+        Ok(positioner)
+    }
+}
+
+impl Dispatch<xdg_popup::XdgPopup, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &xdg_popup::XdgPopup,
+        event: xdg_popup::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        match event {
+            xdg_popup::Event::Configure {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                // The position is relative to the parent's surface, while the
+                // anchor rect was given in its content area: report it there too.
+                let insets = state
+                    .shell_surface(*id)
+                    .and_then(|popup| popup.parent())
+                    .and_then(|parent| state.shell_surface(parent))
+                    .map(|parent| parent.content_insets())
+                    .unwrap_or_default();
+
+                let Some(popup) = state.shell_surface_mut(*id) else {
+                    return;
+                };
+                popup.pending_resize = Some(WindowSize {
+                    width: width.max(1),
+                    height: height.max(1),
+                });
+                if let Role::Popup {
+                    pending_position, ..
+                } = &mut popup.role
+                {
+                    *pending_position = Some((x - insets.left, y - insets.top));
+                }
+            }
+            xdg_popup::Event::Repositioned { .. } => {
+                // The new position comes with the configure that follows.
+                if let Some(ShellSurface {
+                    role: Role::Popup { repositioned, .. },
+                    ..
+                }) = state.shell_surface_mut(*id)
+                {
+                    *repositioned = true;
+                }
+            }
+            // ─────────────────────────────────────────────────────────────
+            // `popup_done`
+            //
+            // The compositor dismissed the popup (e.g, a click outside of a
+            // grabbing popup). It is already unmapped, only the objects are
+            // left to destroy, which `Window::run` does after telling the app.
+            // ─────────────────────────────────────────────────────────────
+            xdg_popup::Event::PopupDone => state.push_event(*id, WindowEvent::PopupDone),
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(AppState: ignore xdg_positioner::XdgPositioner);
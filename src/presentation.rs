@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use rustix::time::{ClockId, clock_gettime};
+use wayland_client::{Connection, Dispatch, QueueHandle, WEnum};
+use wayland_protocols::wp::presentation_time::client::{
+    wp_presentation::{self, WpPresentation},
+    wp_presentation_feedback::{self, Kind, WpPresentationFeedback},
+};
+
+use crate::app::WindowEvent;
+use crate::shell_surface::{ShellSurface, WindowId};
+use crate::state::AppState;
+
+/// Number of frames kept per window to compute [`FrameStats`].
+const FRAME_HISTORY: usize = 120;
+
+/// What happened to a frame once it was submitted, see [`App::frame_presented`](crate::App::frame_presented).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFeedback {
+    /// The frame was shown on screen.
+    Presented(PresentedFrame),
+    /// The frame was never shown (e.g, a newer frame replaced it before the
+    /// next refresh, or the window was hidden).
+    Discarded,
+}
+
+/// Timing of a frame that was shown on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentedFrame {
+    /// When the frame first turned into light, on the compositor's
+    /// presentation clock (usually `CLOCK_MONOTONIC`).
+    pub time: Duration,
+    /// Time from the frame being submitted to it being shown. `None` when
+    /// the presentation clock can't be read.
+    pub latency: Option<Duration>,
+    /// Predicted time until the output's next refresh after [`PresentedFrame::time`].
+    /// `None` when unknown (e.g, variable refresh rate).
+    pub refresh: Option<Duration>,
+    /// Output's vertical retrace counter when the frame was shown, `0` when
+    /// the output has none.
+    pub sequence: u64,
+    pub flags: PresentationFlags,
+}
+
+/// How a frame was presented, to judge how reliable its timing is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PresentationFlags {
+    /// Synchronized to the vertical retrace: the frame can't have torn.
+    pub vsync: bool,
+    /// The timestamp was measured by the display hardware.
+    pub hw_clock: bool,
+    /// The display hardware signaled it started showing the frame,
+    /// rather than the compositor guessing.
+    pub hw_completion: bool,
+    /// The buffer was scanned out as is, without being composited.
+    pub zero_copy: bool,
+}
+
+impl From<WEnum<Kind>> for PresentationFlags {
+    fn from(flags: WEnum<Kind>) -> Self {
+        let kind = match flags {
+            WEnum::Value(kind) => kind,
+            WEnum::Unknown(bits) => Kind::from_bits_truncate(bits),
+        };
+
+        Self {
+            vsync: kind.contains(Kind::Vsync),
+            hw_clock: kind.contains(Kind::HwClock),
+            hw_completion: kind.contains(Kind::HwCompletion),
+            zero_copy: kind.contains(Kind::ZeroCopy),
+        }
+    }
+}
+
+/// Frame timing over the last frames of a window, see [`Window::frame_stats`](crate::Window::frame_stats).
+///
+/// Empty when the compositor doesn't implement `wp_presentation`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Frames shown on screen.
+    pub presented: u32,
+    /// Frames never shown.
+    pub discarded: u32,
+    /// Average time between two frames shown.
+    pub average_interval: Option<Duration>,
+    /// Longest time between two frames shown, the worst stutter.
+    pub max_interval: Option<Duration>,
+    /// Average time from a frame being submitted to it being shown.
+    pub average_latency: Option<Duration>,
+    /// Refreshes of the output that went by without a new frame, from the
+    /// retrace counter. Only meaningful while rendering continuously.
+    pub missed_refreshes: u64,
+    /// Refresh interval reported with the latest frame shown.
+    pub refresh: Option<Duration>,
+}
+
+impl FrameStats {
+    pub(crate) fn from_history(history: &VecDeque<FrameFeedback>) -> Self {
+        let mut stats = Self::default();
+        let mut intervals = Vec::new();
+        let mut latencies = Vec::new();
+        let mut previous: Option<PresentedFrame> = None;
+
+        for feedback in history {
+            let FrameFeedback::Presented(frame) = feedback else {
+                stats.discarded += 1;
+                continue;
+            };
+
+            stats.presented += 1;
+            stats.refresh = frame.refresh;
+            latencies.extend(frame.latency);
+
+            if let Some(previous) = previous {
+                intervals.extend(frame.time.checked_sub(previous.time));
+                if previous.sequence != 0 && frame.sequence != 0 {
+                    stats.missed_refreshes += frame
+                        .sequence
+                        .saturating_sub(previous.sequence)
+                        .saturating_sub(1);
+                }
+            }
+            previous = Some(*frame);
+        }
+
+        stats.average_interval = average(&intervals);
+        stats.max_interval = intervals.iter().max().copied();
+        stats.average_latency = average(&latencies);
+
+        stats
+    }
+}
+
+fn average(durations: &[Duration]) -> Option<Duration> {
+    let count = u32::try_from(durations.len())
+        .ok()
+        .filter(|count| *count > 0)?;
+    Some(durations.iter().sum::<Duration>() / count)
+}
+
+/// User data of a `wp_presentation_feedback`: the window it was requested
+/// for and when the frame was submitted, on the presentation clock.
+pub(crate) struct PendingFeedback {
+    id: WindowId,
+    submitted: Option<Duration>,
+}
+
+// ─────────────────────────────────────────────────────────────
+// Presentation Time
+//
+// Frame callbacks only tell when to draw. `wp_presentation` tells when a
+// frame was actually shown: `wp_presentation.feedback` attaches a
+// `wp_presentation_feedback` to the next commit, which later receives
+// either:
+//
+//   - `presented`, with the time the frame turned into light, the
+//     predicted refresh interval and the output's retrace counter.
+//   - `discarded`, if the frame was never shown.
+//
+// Timestamps are on a clock picked by the compositor (`clock_id`, sent
+// once on bind). Reading that same clock right before presenting gives
+// the latency of each frame.
+// ─────────────────────────────────────────────────────────────
+impl ShellSurface {
+    /// Requests feedback for the frame about to be presented.
+    pub(crate) fn request_presentation_feedback(
+        &self,
+        presentation: &WpPresentation,
+        clock: Option<ClockId>,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        let submitted = clock.map(|clock| {
+            let now = clock_gettime(clock);
+            Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+        });
+
+        // Double-buffered, tied to the commit performed by `present`.
+        presentation.feedback(
+            &self.wl_surface,
+            queue_handle,
+            PendingFeedback {
+                id: self.id,
+                submitted,
+            },
+        );
+    }
+}
+
+impl Dispatch<WpPresentation, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &WpPresentation,
+        event: wp_presentation::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        // Clocks other than these leave latencies unknown.
+        if let wp_presentation::Event::ClockId { clk_id } = event {
+            state.presentation_clock = [ClockId::Monotonic, ClockId::Realtime]
+                .into_iter()
+                .find(|clock| *clock as i32 == clk_id as i32);
+        }
+    }
+}
+
+impl Dispatch<WpPresentationFeedback, PendingFeedback> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &WpPresentationFeedback,
+        event: wp_presentation_feedback::Event,
+        pending: &PendingFeedback,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        let feedback = match event {
+            wp_presentation_feedback::Event::Presented {
+                tv_sec_hi,
+                tv_sec_lo,
+                tv_nsec,
+                refresh,
+                seq_hi,
+                seq_lo,
+                flags,
+            } => {
+                let seconds = (u64::from(tv_sec_hi) << 32) | u64::from(tv_sec_lo);
+                let time = Duration::new(seconds, tv_nsec);
+
+                FrameFeedback::Presented(PresentedFrame {
+                    time,
+                    latency: pending
+                        .submitted
+                        .and_then(|submitted| time.checked_sub(submitted)),
+                    refresh: (refresh != 0).then(|| Duration::from_nanos(refresh.into())),
+                    sequence: (u64::from(seq_hi) << 32) | u64::from(seq_lo),
+                    flags: flags.into(),
+                })
+            }
+            wp_presentation_feedback::Event::Discarded => FrameFeedback::Discarded,
+            // `sync_output` names the output the timing is for.
+            _ => return,
+        };
+
+        // Feedback for frames of a closed window still arrives, as discarded.
+        let Some(shell_surface) = state.shell_surface_mut(pending.id) else {
+            return;
+        };
+
+        if shell_surface.frame_history.len() == FRAME_HISTORY {
+            shell_surface.frame_history.pop_front();
+        }
+        shell_surface.frame_history.push_back(feedback);
+
+        state.push_event(pending.id, WindowEvent::FramePresented(feedback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn presented(time_ms: u64, latency_ms: Option<u64>, sequence: u64) -> FrameFeedback {
+        FrameFeedback::Presented(PresentedFrame {
+            time: Duration::from_millis(time_ms),
+            latency: latency_ms.map(Duration::from_millis),
+            refresh: Some(Duration::from_millis(10)),
+            sequence,
+            flags: PresentationFlags::default(),
+        })
+    }
+
+    #[test]
+    fn frame_stats_from_history() {
+        let history = VecDeque::from([
+            presented(100, Some(4), 10),
+            FrameFeedback::Discarded,
+            presented(110, Some(6), 11),
+            // Two refreshes went by without a frame.
+            presented(140, None, 14),
+            FrameFeedback::Discarded,
+            // No retrace counter: not counted as missed.
+            presented(150, Some(8), 0),
+            presented(160, Some(6), 16),
+        ]);
+
+        let stats = FrameStats::from_history(&history);
+
+        assert_eq!(stats.presented, 5);
+        assert_eq!(stats.discarded, 2);
+        assert_eq!(stats.average_interval, Some(Duration::from_millis(15)));
+        assert_eq!(stats.max_interval, Some(Duration::from_millis(30)));
+        assert_eq!(stats.average_latency, Some(Duration::from_millis(6)));
+        assert_eq!(stats.missed_refreshes, 2);
+        assert_eq!(stats.refresh, Some(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn frame_stats_skip_backwards_timestamps() {
+        let history = VecDeque::from([presented(100, None, 5), presented(90, None, 3)]);
+
+        let stats = FrameStats::from_history(&history);
+
+        assert_eq!(stats.average_interval, None);
+        assert_eq!(stats.max_interval, None);
+        assert_eq!(stats.average_latency, None);
+        assert_eq!(stats.missed_refreshes, 0);
+    }
+
+    #[test]
+    fn frame_stats_from_empty_history() {
+        assert_eq!(
+            FrameStats::from_history(&VecDeque::new()),
+            FrameStats::default()
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use wayland_client::QueueHandle;
+
+use crate::app::{App, RenderContext};
+use crate::csd::Layout;
+use crate::error::WindowError;
+use crate::shell_surface::{ShellSurface, WindowId};
+use crate::state::AppState;
+use crate::window::WindowSize;
+
+impl ShellSurface {
+    // ─────────────────────────────────────────────────────────────
+    // Frame Acquisition
+    //
+    // `get_current_texture()` can fail for reasons that are part of
+    // normal window life:
+    //
+    //   - `Lost` / `Outdated`: the surface changed under us (e.g, the compositor
+    //     resized it). Reconfiguring with the current size and retrying usually
+    //     fixes it, if not the frame is skipped.
+    //   - `Timeout`: no image became available in time. The frame is skipped.
+    //
+    // Anything else (`OutOfMemory`, `Other`) is unrecoverable and is
+    // returned to the caller.
+    // ─────────────────────────────────────────────────────────────
+    fn acquire_frame(
+        &self,
+        device: &wgpu::Device,
+    ) -> Result<Option<wgpu::SurfaceTexture>, WindowError> {
+        match self.surface.get_current_texture() {
+            Ok(frame) => Ok(Some(frame)),
+            Err(wgpu::SurfaceError::Timeout) => Ok(None),
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                if let Some(size) = self.size {
+                    self.configure_wgpu(device, size.width, size.height);
+                }
+
+                // Still out of date (e.g, mid interactive resize): the
+                // frame is skipped, the next one gets another chance.
+                match self.surface.get_current_texture() {
+                    Ok(frame) => Ok(Some(frame)),
+                    Err(
+                        wgpu::SurfaceError::Timeout
+                        | wgpu::SurfaceError::Lost
+                        | wgpu::SurfaceError::Outdated,
+                    ) => Ok(None),
+                    Err(error) => Err(error.into()),
+                }
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Frame Callbacks
+    //
+    // `wl_surface.frame` asks the compositor to send a `done` event on a
+    // `wl_callback` once it is a good time to start drawing the next frame.
+    //
+    // The request is double-buffered: it only takes effect on the next
+    // `wl_surface.commit`, which `wgpu` performs when presenting.
+    //
+    // The compositor stops sending `done` events while the surface is hidden
+    // (minimized, on another workspace), so rendering pauses for free.
+    // ─────────────────────────────────────────────────────────────
+    fn request_frame_callback(&mut self, queue_handle: &QueueHandle<AppState>) {
+        self.wl_surface.frame(queue_handle, self.id);
+        self.frame_pending = true;
+    }
+
+    /// `true` when the window is configured and the compositor is ready for a new frame.
+    pub(crate) fn should_draw(&self) -> bool {
+        self.configured && self.needs_redraw && !self.frame_pending
+    }
+}
+
+impl AppState {
+    pub(crate) fn draw(
+        &mut self,
+        id: WindowId,
+        queue_handle: &QueueHandle<AppState>,
+        app: &mut impl App,
+    ) -> Result<(), WindowError> {
+        let (Some(shell_surface), Some(wgpu_state)) = (
+            self.surfaces
+                .iter_mut()
+                .find(|shell_surface| shell_surface.id == id),
+            self.wgpu_state.as_ref(),
+        ) else {
+            return Ok(());
+        };
+
+        if !shell_surface.should_draw() {
+            return Ok(());
+        }
+
+        let Some(frame) = shell_surface.acquire_frame(wgpu_state.device())? else {
+            // No image this time around: commit without a new buffer so
+            // the compositor still wakes us up on its next frame.
+            shell_surface.request_frame_callback(queue_handle);
+            shell_surface.wl_surface.commit();
+            return Ok(());
+        };
+
+        shell_surface.needs_redraw = false;
+        shell_surface.request_frame_callback(queue_handle);
+
+        let view = frame.texture.create_view(&Default::default());
+
+        let encoder = wgpu_state
+            .device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("draw_encoder"),
+            });
+
+        // The frame is in physical pixels. The content area is what is left
+        // once the decorations are taken out, so rounding never makes it
+        // spill outside the frame.
+        let size = shell_surface.size.unwrap_or(shell_surface.initial_size);
+        let frame_size = shell_surface.physical_size(size);
+        let insets = shell_surface.content_insets();
+        let (left, top) = (
+            shell_surface.to_physical(insets.left),
+            shell_surface.to_physical(insets.top),
+        );
+        let (right, bottom) = (
+            shell_surface.to_physical(insets.right),
+            shell_surface.to_physical(insets.bottom),
+        );
+
+        let mut context = RenderContext {
+            wgpu_state,
+            window_id: id,
+            format: shell_surface.settings.format,
+            view,
+            encoder,
+            size: WindowSize {
+                width: (frame_size.width - left - right).max(1),
+                height: (frame_size.height - top - bottom).max(1),
+            },
+            offset: (left, top),
+            scale: shell_surface.scale,
+        };
+
+        app.render(&mut context);
+
+        if shell_surface.has_client_decorations() {
+            let layout = Layout::new(shell_surface);
+            if let Some(decorations) = shell_surface.decorations.as_mut() {
+                decorations.render(&mut context, layout);
+            }
+        }
+
+        wgpu_state.queue().submit(Some(context.encoder.finish()));
+
+        if let Some(presentation) = self.presentation.as_ref() {
+            shell_surface.request_presentation_feedback(
+                presentation,
+                self.presentation_clock,
+                queue_handle,
+            );
+        }
+        frame.present();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use wayland_client::{
+    Connection, Dispatch, Proxy, QueueHandle, delegate_noop,
+    protocol::{wl_output, wl_surface},
+};
+use wayland_protocols::wp::fractional_scale::v1::client::{
+    wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1,
+    wp_fractional_scale_v1::{self, WpFractionalScaleV1},
+};
+use wayland_protocols::wp::viewporter::client::{
+    wp_viewport::WpViewport, wp_viewporter::WpViewporter,
+};
+
+use crate::app::WindowEvent;
+use crate::shell_surface::{ShellSurface, WindowId};
+use crate::state::AppState;
+use crate::window::WindowSize;
+
+// ─────────────────────────────────────────────────────────────
+// Fractional Scaling
+//
+// Sizes negotiated with the compositor are logical: a 800x600 window
+// covers 1200x900 pixels on an output scaled by 1.5. Rendering at the
+// logical size leaves the compositor to upscale the result, which
+// looks blurry.
+//
+// Two protocols avoid that:
+//
+//   1. `wp_fractional_scale_v1` tells us the scale the compositor would
+//      like the surface drawn at, in 120ths (e.g, `180` for 1.5).
+//   2. `wp_viewport` maps a buffer of any size onto the surface:
+//      `set_destination` gives the surface its logical size, whatever
+//      the size of the buffer attached to it.
+//
+// The `wgpu::Surface` is then configured at the physical size (the
+// logical size times the scale, rounded half away from zero), and the
+// viewport scales it back down to the logical size.
+//
+// Without either global, the scale stays at `1.0`.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn init_scaling(
+        &self,
+        shell_surface: &mut ShellSurface,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        let (Some(manager), Some(viewporter)) = (
+            self.fractional_scale_manager.as_ref(),
+            self.viewporter.as_ref(),
+        ) else {
+            return;
+        };
+
+        let wl_surface = &shell_surface.wl_surface;
+        shell_surface.viewport = Some(viewporter.get_viewport(wl_surface, queue_handle, ()));
+        shell_surface.fractional_scale =
+            Some(manager.get_fractional_scale(wl_surface, queue_handle, shell_surface.id));
+    }
+}
+
+impl ShellSurface {
+    /// Switches to a new scale, reconfiguring the `wgpu::Surface` for it.
+    ///
+    /// Not part of a configure sequence: the new scale applies right away,
+    /// with the next frame drawn at the new physical size.
+    pub(crate) fn set_scale(&mut self, device: &wgpu::Device, scale: f64) -> bool {
+        if scale <= 0.0 || self.scale == scale {
+            return false;
+        }
+
+        self.scale = scale;
+        if let Some(size) = self.size {
+            self.configure_wgpu(device, size.width, size.height);
+            self.needs_redraw = true;
+        }
+
+        true
+    }
+
+    /// Converts a logical length to physical pixels at the current scale.
+    pub(crate) fn to_physical(&self, length: i32) -> i32 {
+        (length as f64 * self.scale).round() as i32
+    }
+
+    pub(crate) fn physical_size(&self, size: WindowSize) -> WindowSize {
+        WindowSize {
+            width: self.to_physical(size.width).max(1),
+            height: self.to_physical(size.height).max(1),
+        }
+    }
+}
+
+impl Dispatch<WpFractionalScaleV1, WindowId> for AppState {
+    fn event(
+        state: &mut Self,
+        _: &WpFractionalScaleV1,
+        event: wp_fractional_scale_v1::Event,
+        id: &WindowId,
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        let wp_fractional_scale_v1::Event::PreferredScale { scale } = event else {
+            return;
+        };
+        let scale = scale as f64 / 120.0;
+
+        let (Some(shell_surface), Some(wgpu_state)) = (
+            state
+                .surfaces
+                .iter_mut()
+                .find(|shell_surface| shell_surface.id == *id),
+            state.wgpu_state.as_ref(),
+        ) else {
+            return;
+        };
+
+        if shell_surface.set_scale(wgpu_state.device(), scale) {
+            state.push_event(*id, WindowEvent::ScaleChanged(scale));
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Integer Buffer Scale
+//
+// Without fractional scaling, HiDPI is handled with an integer scale:
+// the buffer is drawn `n` times larger and `wl_surface.set_buffer_scale(n)`
+// tells the compositor to map it back to the logical size.
+//
+// The scale to use comes from, in order of preference:
+//
+//   1. `wl_surface.preferred_buffer_scale` (version 6), the compositor's
+//      own pick for the surface.
+//   2. The highest scale among the outputs the surface is on, tracked
+//      through `wl_surface.enter` / `leave`. It looks sharp on the densest
+//      one and gets downscaled on the others.
+//
+// Surfaces using fractional scaling must keep a buffer scale of 1,
+// so they are left alone.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn update_buffer_scale(&mut self, id: WindowId) {
+        let Some(shell_surface) = self
+            .surfaces
+            .iter()
+            .find(|shell_surface| shell_surface.id == id)
+            .filter(|shell_surface| shell_surface.fractional_scale.is_none())
+        else {
+            return;
+        };
+
+        let factor = shell_surface.preferred_buffer_scale.unwrap_or_else(|| {
+            self.outputs
+                .iter()
+                .filter(|output| shell_surface.outputs.contains(&output.wl_output))
+                .map(|output| output.info.scale)
+                .max()
+                .unwrap_or(1)
+        });
+
+        let (Some(shell_surface), Some(wgpu_state)) = (
+            self.surfaces
+                .iter_mut()
+                .find(|shell_surface| shell_surface.id == id),
+            self.wgpu_state.as_ref(),
+        ) else {
+            return;
+        };
+
+        // `set_buffer_scale` only exists since version 3.
+        if shell_surface.wl_surface.version() < 3 {
+            return;
+        }
+
+        if shell_surface.set_scale(wgpu_state.device(), factor as f64) {
+            // Double-buffered, applied along with the first frame at the new size.
+            shell_surface.wl_surface.set_buffer_scale(factor);
+            self.push_event(id, WindowEvent::ScaleChanged(factor as f64));
+        }
+    }
+
+    /// Rescales every surface on `wl_output` after its scale may have changed.
+    pub(crate) fn update_buffer_scales(&mut self, wl_output: &wl_output::WlOutput) {
+        let ids: Vec<WindowId> = self
+            .surfaces
+            .iter()
+            .filter(|shell_surface| shell_surface.outputs.contains(wl_output))
+            .map(|shell_surface| shell_surface.id)
+            .collect();
+
+        for id in ids {
+            self.update_buffer_scale(id);
+        }
+    }
+}
+
+impl Dispatch<wl_surface::WlSurface, ()> for AppState {
+    fn event(
+        state: &mut Self,
+        wl_surface: &wl_surface::WlSurface,
+        event: wl_surface::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<AppState>,
+    ) {
+        let Some(id) = state.window_of(wl_surface) else {
+            return;
+        };
+        let Some(shell_surface) = state.shell_surface_mut(id) else {
+            return;
+        };
+
+        match event {
+            wl_surface::Event::Enter { output } => {
+                if !shell_surface.outputs.contains(&output) {
+                    shell_surface.outputs.push(output);
+                }
+            }
+            wl_surface::Event::Leave { output } => {
+                shell_surface.outputs.retain(|entered| entered != &output);
+            }
+            wl_surface::Event::PreferredBufferScale { factor } => {
+                shell_surface.preferred_buffer_scale = Some(factor.max(1));
+            }
+            _ => return,
+        }
+
+        state.update_buffer_scale(id);
+    }
+}
+
+delegate_noop!(AppState: ignore WpFractionalScaleManagerV1);
+delegate_noop!(AppState: ignore WpViewporter);
+delegate_noop!(AppState: ignore WpViewport);
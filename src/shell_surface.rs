@@ -0,0 +1,544 @@
+use std::collections::VecDeque;
+
+use wayland_client::{
+    Connection, QueueHandle,
+    protocol::{wl_output, wl_subsurface, wl_surface},
+};
+use wayland_protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use wayland_protocols::wp::tearing_control::v1::client::wp_tearing_control_v1::WpTearingControlV1;
+use wayland_protocols::wp::viewporter::client::wp_viewport::WpViewport;
+use wayland_protocols::xdg::decoration::zv1::client::zxdg_toplevel_decoration_v1::ZxdgToplevelDecorationV1;
+use wayland_protocols::xdg::shell::client::{xdg_popup, xdg_surface, xdg_toplevel};
+use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_surface_v1::ZwlrLayerSurfaceV1;
+
+use crate::csd::{ClientDecorations, Insets};
+use crate::decoration::DecorationMode;
+use crate::error::WindowError;
+use crate::presentation::FrameFeedback;
+use crate::state::AppState;
+use crate::surface_config::{SurfaceConfig, SurfaceSettings};
+use crate::window::{WindowBuilder, WindowSize, WindowState, WmCapabilities};
+
+/// Identifies one of the windows opened on a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowId(pub(crate) u32);
+
+// ─────────────────────────────────────────────────────────────
+// Shell Surfaces
+//
+// Everything that belongs to a single window: its Wayland objects,
+// its `wgpu::Surface`, and the configure state negotiated with the
+// compositor for it.
+//
+// Wayland objects created for a window carry its `WindowId` as user
+// data, so their `Dispatch` impls know which `ShellSurface` an event is for.
+// Input events name a `wl_surface` instead, which is matched against
+// each window's surface.
+// ─────────────────────────────────────────────────────────────
+pub(crate) struct ShellSurface {
+    pub(crate) id: WindowId,
+
+    //Wayland objects
+    pub(crate) wl_surface: wl_surface::WlSurface,
+    pub(crate) role: Role,
+    pub(crate) viewport: Option<WpViewport>,
+    pub(crate) fractional_scale: Option<WpFractionalScaleV1>,
+    pub(crate) tearing_control: Option<WpTearingControlV1>,
+
+    //GPU
+    pub(crate) surface: wgpu::Surface<'static>,
+    pub(crate) settings: SurfaceSettings,
+    pub(crate) scale: f64,
+    pub(crate) outputs: Vec<wl_output::WlOutput>,
+    pub(crate) preferred_buffer_scale: Option<i32>,
+
+    //Window Config
+    pub(crate) initial_size: WindowSize,
+    pub(crate) min_size: Option<WindowSize>,
+    pub(crate) max_size: Option<WindowSize>,
+    pub(crate) bounds: Option<WindowSize>,
+    pub(crate) configured: bool,
+    pub(crate) size: Option<WindowSize>,
+    pub(crate) pending_resize: Option<WindowSize>,
+    pub(crate) decoration_mode: DecorationMode,
+    pub(crate) pending_decoration_mode: Option<DecorationMode>,
+    pub(crate) decorations: Option<ClientDecorations>,
+    pub(crate) window_state: WindowState,
+    pub(crate) pending_window_state: Option<WindowState>,
+    pub(crate) wm_capabilities: WmCapabilities,
+    pub(crate) pending_wm_capabilities: Option<WmCapabilities>,
+
+    //Frame pacing
+    pub(crate) needs_redraw: bool,
+    pub(crate) frame_pending: bool,
+    pub(crate) frame_history: VecDeque<FrameFeedback>,
+}
+
+// ─────────────────────────────────────────────────────────────
+// Roles
+//
+// A `wl_surface` is only a rectangle of pixels until it is given a role,
+// which tells the compositor how to place it:
+//
+//   - `xdg_toplevel` → a regular window, managed by the user.
+//   - `xdg_popup`    → a short-lived surface (menu, tooltip) placed
+//                      relative to its parent by an `xdg_positioner`.
+//   - layer surface  → a desktop component (panel, wallpaper) placed
+//                      on an output, see `layer_shell.rs`.
+//   - `wl_subsurface` → a child drawn within another surface, see
+//                      `subsurface.rs`.
+//
+// A surface keeps the role it was given for its whole lifetime.
+// ─────────────────────────────────────────────────────────────
+pub(crate) enum Role {
+    Toplevel {
+        xdg_surface: xdg_surface::XdgSurface,
+        xdg_toplevel: xdg_toplevel::XdgToplevel,
+        decoration: Option<ZxdgToplevelDecorationV1>,
+    },
+    Popup {
+        xdg_surface: xdg_surface::XdgSurface,
+        xdg_popup: xdg_popup::XdgPopup,
+        parent: WindowId,
+        position: (i32, i32),
+        pending_position: Option<(i32, i32)>,
+        repositioned: bool,
+    },
+    Layer {
+        layer_surface: ZwlrLayerSurfaceV1,
+    },
+    Subsurface {
+        wl_subsurface: wl_subsurface::WlSubsurface,
+        parent: WindowId,
+        /// Position within the parent's content area.
+        position: (i32, i32),
+    },
+}
+
+impl ShellSurface {
+    pub(crate) fn new(
+        id: WindowId,
+        wl_surface: wl_surface::WlSurface,
+        role: Role,
+        surface: wgpu::Surface<'static>,
+        settings: SurfaceSettings,
+        initial_size: WindowSize,
+    ) -> Self {
+        Self {
+            id,
+            wl_surface,
+            role,
+            viewport: None,
+            fractional_scale: None,
+            tearing_control: None,
+            surface,
+            settings,
+            scale: 1.0,
+            outputs: Vec::new(),
+            preferred_buffer_scale: None,
+            initial_size,
+            min_size: None,
+            max_size: None,
+            bounds: None,
+            configured: false,
+            size: None,
+            pending_resize: None,
+            decoration_mode: DecorationMode::ClientSide,
+            pending_decoration_mode: None,
+            decorations: None,
+            window_state: WindowState::default(),
+            pending_window_state: None,
+            wm_capabilities: WmCapabilities::default(),
+            pending_wm_capabilities: None,
+            needs_redraw: true,
+            frame_pending: false,
+            frame_history: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn xdg_toplevel(&self) -> Option<&xdg_toplevel::XdgToplevel> {
+        match &self.role {
+            Role::Toplevel { xdg_toplevel, .. } => Some(xdg_toplevel),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn xdg_popup(&self) -> Option<&xdg_popup::XdgPopup> {
+        match &self.role {
+            Role::Popup { xdg_popup, .. } => Some(xdg_popup),
+            _ => None,
+        }
+    }
+
+    /// The window a popup or subsurface is attached to.
+    pub(crate) fn parent(&self) -> Option<WindowId> {
+        match self.role {
+            Role::Popup { parent, .. } | Role::Subsurface { parent, .. } => Some(parent),
+            _ => None,
+        }
+    }
+
+    /// `true` when the built-in decorations are enabled and the compositor left decorating to us.
+    ///
+    /// Fullscreen windows are never decorated.
+    pub(crate) fn has_client_decorations(&self) -> bool {
+        self.decorations.is_some()
+            && self.decoration_mode == DecorationMode::ClientSide
+            && !self.window_state.fullscreen
+    }
+
+    pub(crate) fn content_insets(&self) -> Insets {
+        if self.has_client_decorations() {
+            ClientDecorations::insets(self.window_state)
+        } else {
+            Insets::default()
+        }
+    }
+
+    /// Size left to the app once the decorations are taken out.
+    pub(crate) fn content_size(&self) -> Option<WindowSize> {
+        self.size.map(|size| self.content_insets().shrink(size))
+    }
+
+    /// Size to use when the compositor lets the client choose.
+    pub(crate) fn preferred_size(&self) -> WindowSize {
+        clamp_size(self.initial_size, self.bounds, self.min_size, self.max_size)
+    }
+
+    /// Configures the `wgpu::Surface` for a logical size of `width` x `height`.
+    ///
+    /// The surface gets the matching physical size, which the viewport maps
+    /// back to the logical size when fractional scaling is in use.
+    pub(crate) fn configure_wgpu(&self, device: &wgpu::Device, width: i32, height: i32) {
+        let physical = self.physical_size(WindowSize { width, height });
+
+        let surface_configuration = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: self.settings.format,
+            width: physical.width as u32,
+            height: physical.height as u32,
+            present_mode: self.settings.present_mode,
+            alpha_mode: self.settings.alpha_mode,
+            view_formats: self.settings.view_formats.clone(),
+            desired_maximum_frame_latency: self.settings.frame_latency,
+        };
+
+        self.surface.configure(device, &surface_configuration);
+
+        // Double-buffered, applied along with the next frame.
+        if let Some(viewport) = &self.viewport {
+            viewport.set_destination(width.max(1), height.max(1));
+        }
+    }
+}
+
+/// Shrinks `size` to fit the compositor's `bounds`, then keeps it within
+/// the `min`/`max` constraints, which take precedence.
+///
+/// A `max` dimension of `0` leaves that dimension unconstrained.
+fn clamp_size(
+    mut size: WindowSize,
+    bounds: Option<WindowSize>,
+    min: Option<WindowSize>,
+    max: Option<WindowSize>,
+) -> WindowSize {
+    if let Some(bounds) = bounds {
+        size.width = size.width.min(bounds.width);
+        size.height = size.height.min(bounds.height);
+    }
+    if let Some(max) = max {
+        if max.width > 0 {
+            size.width = size.width.min(max.width);
+        }
+        if max.height > 0 {
+            size.height = size.height.min(max.height);
+        }
+    }
+    if let Some(min) = min {
+        size.width = size.width.max(min.width);
+        size.height = size.height.max(min.height);
+    }
+
+    WindowSize {
+        width: size.width.max(1),
+        height: size.height.max(1),
+    }
+}
+
+impl AppState {
+    pub(crate) fn open_toplevel(
+        &mut self,
+        builder: WindowBuilder,
+        connection: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<WindowId, WindowError> {
+        // ─────────────────────────────────────────────────────────────
+        // `xdg_wm_base`
+        //
+        // The `xdg_wm_base` global is exposed by the compositor to allow clients
+        // to turn `wl_surface`s into "windows" in a desktop environment.
+        //
+        // It acts as the entry point for the XDG shell protocol and is responsible
+        // for creating `xdg_surface` objects.
+        // ─────────────────────────────────────────────────────────────
+        let wm_base = self
+            .wm_base
+            .clone()
+            .ok_or(WindowError::MissingGlobal("xdg_wm_base"))?;
+
+        // Tearing needs `Immediate`, tried before the window's own present modes.
+        let mut surface_config = builder.surface_config;
+        if builder.tearing {
+            surface_config
+                .present_modes
+                .insert(0, wgpu::PresentMode::Immediate);
+        }
+
+        let (wl_surface, surface, settings) =
+            self.create_surfaces(connection, &surface_config, queue_handle)?;
+
+        let id = self.next_window_id();
+
+        // ─────────────────────────────────────────────────────────────
+        // `xdg_surface`
+        //
+        // The `xdg_surface` protocol is built on top of `wl_surface`.
+        // It enables desktop-style window management (e.g, moving, resizing).
+        //
+        // To create an interactive window:
+        //
+        //   1. You must first create an `xdg_surface` from a `wl_surface`.
+        //   2. Then, immediately assign it a role (e.g, `get_toplevel()`. `get_popup()`).
+        //   3. Finally, perform an initial commit — This initial commit CANNOT have a buffer attached.
+        //
+        // ─────────────────────────────────────────────────────────────
+        let xdg_surface = wm_base.get_xdg_surface(&wl_surface, queue_handle, id);
+        let xdg_toplevel = xdg_surface.get_toplevel(queue_handle, id);
+
+        xdg_toplevel.set_title(builder.title);
+        if let Some(app_id) = builder.app_id {
+            xdg_toplevel.set_app_id(app_id);
+        }
+
+        // ─────────────────────────────────────────────────────────────
+        // Size Constraints
+        //
+        // Double-buffered state, applied by the initial commit below.
+        // The compositor uses them to limit interactive resizes. `0` on
+        // either axis means "no constraint".
+        // ─────────────────────────────────────────────────────────────
+        if let Some(min) = builder.min_size {
+            xdg_toplevel.set_min_size(min.width, min.height);
+        }
+        if let Some(max) = builder.max_size {
+            xdg_toplevel.set_max_size(max.width, max.height);
+        }
+
+        // Decorations must be negotiated before the initial commit.
+        let decoration =
+            self.init_decoration(&xdg_toplevel, builder.decoration_mode, id, queue_handle);
+
+        wl_surface.commit();
+
+        let mut shell_surface = ShellSurface::new(
+            id,
+            wl_surface,
+            Role::Toplevel {
+                xdg_surface,
+                xdg_toplevel,
+                decoration,
+            },
+            surface,
+            settings,
+            builder.size,
+        );
+        shell_surface.min_size = builder.min_size;
+        shell_surface.max_size = builder.max_size;
+        shell_surface.decorations = builder.client_side_decorations.then(ClientDecorations::new);
+        self.init_scaling(&mut shell_surface, queue_handle);
+        if builder.tearing {
+            self.init_tearing(&mut shell_surface, queue_handle);
+        }
+        self.surfaces.push(shell_surface);
+
+        Ok(id)
+    }
+
+    pub(crate) fn next_window_id(&mut self) -> WindowId {
+        let id = WindowId(self.next_window_id);
+        self.next_window_id += 1;
+        id
+    }
+
+    /// Creates a `wl_surface` and the `wgpu::Surface` drawing to it, before any role is given.
+    pub(crate) fn create_surfaces(
+        &mut self,
+        connection: &Connection,
+        surface_config: &SurfaceConfig,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<
+        (
+            wl_surface::WlSurface,
+            wgpu::Surface<'static>,
+            SurfaceSettings,
+        ),
+        WindowError,
+    > {
+        // ─────────────────────────────────────────────────────────────
+        // `wl_surface`
+        //
+        // The `wl_surface` is a low-level rectangle area that clients use to:
+        //
+        //   - Attach graphical content via `wl_buffer`s
+        //   - Receive input events
+        //   - Define local coordinate systems
+        //
+        // ─────────────────────────────────────────────────────────────
+        let wl_surface = self
+            .compositor
+            .as_ref()
+            .ok_or(WindowError::MissingGlobal("wl_compositor"))?
+            .create_surface(queue_handle, ());
+
+        // ─────────────────────────────────────────────────────────────
+        // Setup Order
+        //
+        // WGPU is set up before the `xdg_surface` gets its initial commit,
+        // so the surface is ready to be configured by the time the
+        // compositor's first `configure` event arrives.
+        // ─────────────────────────────────────────────────────────────
+        match self.create_wgpu_surface(connection, &wl_surface, surface_config) {
+            Ok((surface, settings)) => Ok((wl_surface, surface, settings)),
+            Err(error) => {
+                wl_surface.destroy();
+                Err(error)
+            }
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Closing a Window
+    //
+    // Objects are destroyed children first: popups and subsurfaces opened
+    // on the window, then the `wgpu::Surface` and the decoration object, then the role
+    // objects, and the `wl_surface` last.
+    //
+    // Popups must go before their parent: destroying a popup that is not
+    // the topmost one is a protocol error. Destroying the role object
+    // unmaps the surface right away.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn close_surface(&mut self, id: WindowId) {
+        let children: Vec<WindowId> = self
+            .surfaces
+            .iter()
+            .filter(|shell_surface| shell_surface.parent() == Some(id))
+            .map(|shell_surface| shell_surface.id)
+            .collect();
+        for child in children {
+            self.close_surface(child);
+        }
+
+        let Some(index) = self
+            .surfaces
+            .iter()
+            .position(|shell_surface| shell_surface.id == id)
+        else {
+            return;
+        };
+        let shell_surface = self.surfaces.remove(index);
+
+        drop(shell_surface.surface);
+        if let Some(viewport) = shell_surface.viewport {
+            viewport.destroy();
+        }
+        if let Some(fractional_scale) = shell_surface.fractional_scale {
+            fractional_scale.destroy();
+        }
+        if let Some(tearing_control) = shell_surface.tearing_control {
+            tearing_control.destroy();
+        }
+        match shell_surface.role {
+            Role::Toplevel {
+                xdg_surface,
+                xdg_toplevel,
+                decoration,
+            } => {
+                if let Some(decoration) = decoration {
+                    decoration.destroy();
+                }
+                xdg_toplevel.destroy();
+                xdg_surface.destroy();
+            }
+            Role::Popup {
+                xdg_surface,
+                xdg_popup,
+                ..
+            } => {
+                xdg_popup.destroy();
+                xdg_surface.destroy();
+            }
+            Role::Layer { layer_surface } => layer_surface.destroy(),
+            Role::Subsurface { wl_subsurface, .. } => wl_subsurface.destroy(),
+        }
+        shell_surface.wl_surface.destroy();
+
+        self.events.retain(|(event_id, _)| *event_id != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn size(width: i32, height: i32) -> WindowSize {
+        WindowSize { width, height }
+    }
+
+    #[test]
+    fn clamp_size_unconstrained() {
+        assert_eq!(clamp_size(size(800, 600), None, None, None), size(800, 600));
+    }
+
+    #[test]
+    fn clamp_size_fits_bounds() {
+        let clamped = clamp_size(size(800, 600), Some(size(640, 1000)), None, None);
+        assert_eq!(clamped, size(640, 600));
+    }
+
+    #[test]
+    fn clamp_size_max_applies_after_bounds() {
+        let clamped = clamp_size(
+            size(800, 600),
+            Some(size(700, 500)),
+            None,
+            Some(size(400, 550)),
+        );
+        assert_eq!(clamped, size(400, 500));
+    }
+
+    #[test]
+    fn clamp_size_min_takes_precedence() {
+        let clamped = clamp_size(
+            size(800, 600),
+            Some(size(300, 200)),
+            Some(size(500, 250)),
+            Some(size(400, 400)),
+        );
+        assert_eq!(clamped, size(500, 250));
+    }
+
+    #[test]
+    fn clamp_size_zero_max_is_unconstrained() {
+        let clamped = clamp_size(size(800, 600), None, None, Some(size(0, 300)));
+        assert_eq!(clamped, size(800, 300));
+
+        let clamped = clamp_size(size(800, 600), None, None, Some(size(0, 0)));
+        assert_eq!(clamped, size(800, 600));
+    }
+
+    #[test]
+    fn clamp_size_is_at_least_one() {
+        let clamped = clamp_size(size(800, 600), Some(size(0, 0)), None, None);
+        assert_eq!(clamped, size(1, 1));
+    }
+}
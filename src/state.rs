@@ -0,0 +1,269 @@
+use std::ptr::NonNull;
+
+use raw_window_handle::{
+    RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle,
+};
+use rustix::time::ClockId;
+use wayland_client::{
+    Connection, Proxy,
+    protocol::{wl_compositor, wl_seat, wl_subcompositor, wl_surface},
+};
+use wayland_protocols::wp::fractional_scale::v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1;
+use wayland_protocols::wp::presentation_time::client::wp_presentation::WpPresentation;
+use wayland_protocols::wp::tearing_control::v1::client::wp_tearing_control_manager_v1::WpTearingControlManagerV1;
+use wayland_protocols::wp::viewporter::client::wp_viewporter::WpViewporter;
+use wayland_protocols::xdg::decoration::zv1::client::zxdg_decoration_manager_v1::ZxdgDecorationManagerV1;
+use wayland_protocols::xdg::shell::client::xdg_wm_base;
+use wayland_protocols_wlr::layer_shell::v1::client::zwlr_layer_shell_v1::ZwlrLayerShellV1;
+use wgpu::SurfaceTargetUnsafe;
+
+use crate::app::WindowEvent;
+use crate::error::WindowError;
+use crate::input::SeatState;
+use crate::output::Output;
+use crate::shell_surface::{ShellSurface, WindowId};
+use crate::surface_config::{SurfaceConfig, SurfaceSettings};
+
+// Application State
+//
+// The `AppState` struct holds all the application-level state,
+// including Wayland objects, window configuration, and GPU context.
+//
+// ─────────────────────────────────────────────────────────────
+//
+// The core event dispatching logic is built around the `EventQueue`.
+// Receiving and processing events is a two-step process:
+//
+//   1. Events are read from the Wayland socket and assigned to an `EventQueue`.
+//   2. The queue then dispatches these events by calling the appropriate
+//      `Dispatch::event()` implementation on the provided `State`.
+//
+// The design ensures that your application's state can be accessed and
+// mutated directly during event handling — without needing synchronization.
+// This helps reduce overhead and simplifies logic.
+//
+// ─────────────────────────────────────────────────────────────
+//
+// Several windows can share one connection. Everything specific to a
+// window lives in its `ShellSurface`, while globals, input devices and the
+// GPU device are shared by all of them.
+//
+// ─────────────────────────────────────────────────────────────
+pub(crate) struct AppState {
+    pub(crate) running: bool,
+    pub(crate) events: Vec<(WindowId, WindowEvent)>,
+    //Wayland globals
+    pub(crate) globals: Vec<BoundGlobal>,
+    pub(crate) compositor: Option<wl_compositor::WlCompositor>,
+    pub(crate) subcompositor: Option<wl_subcompositor::WlSubcompositor>,
+    pub(crate) wm_base: Option<xdg_wm_base::XdgWmBase>,
+    pub(crate) decoration_manager: Option<ZxdgDecorationManagerV1>,
+    pub(crate) layer_shell: Option<ZwlrLayerShellV1>,
+    pub(crate) fractional_scale_manager: Option<WpFractionalScaleManagerV1>,
+    pub(crate) viewporter: Option<WpViewporter>,
+    pub(crate) presentation: Option<WpPresentation>,
+    pub(crate) tearing_control_manager: Option<WpTearingControlManagerV1>,
+    /// Clock `wp_presentation` timestamps are on, if it can be read.
+    pub(crate) presentation_clock: Option<ClockId>,
+
+    //Outputs
+    pub(crate) outputs: Vec<Output>,
+
+    //Input
+    pub(crate) seats: Vec<SeatState>,
+    pub(crate) input_seat: Option<wl_seat::WlSeat>,
+
+    //Windows
+    pub(crate) surfaces: Vec<ShellSurface>,
+    pub(crate) next_window_id: u32,
+
+    //GPU
+    pub(crate) wgpu_state: Option<WgpuState>,
+}
+
+/// A global bound from the registry, remembered to handle its removal.
+pub(crate) struct BoundGlobal {
+    pub(crate) name: u32,
+    pub(crate) interface: String,
+}
+
+/// The GPU context shared by every window on a connection.
+pub struct WgpuState {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl WgpuState {
+    pub fn instance(&self) -> &wgpu::Instance {
+        &self.instance
+    }
+
+    pub fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    pub fn device(&self) -> &wgpu::Device {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.queue
+    }
+}
+
+impl AppState {
+    pub(crate) fn new() -> Self {
+        Self {
+            running: true,
+            events: Vec::new(),
+            globals: Vec::new(),
+            compositor: None,
+            subcompositor: None,
+            wm_base: None,
+            decoration_manager: None,
+            layer_shell: None,
+            fractional_scale_manager: None,
+            viewporter: None,
+            presentation: None,
+            presentation_clock: None,
+            tearing_control_manager: None,
+            outputs: Vec::new(),
+            seats: Vec::new(),
+            input_seat: None,
+            surfaces: Vec::new(),
+            next_window_id: 0,
+            wgpu_state: None,
+        }
+    }
+
+    pub(crate) fn push_event(&mut self, id: WindowId, event: WindowEvent) {
+        self.events.push((id, event));
+    }
+
+    pub(crate) fn shell_surface(&self, id: WindowId) -> Option<&ShellSurface> {
+        self.surfaces
+            .iter()
+            .find(|shell_surface| shell_surface.id == id)
+    }
+
+    pub(crate) fn shell_surface_mut(&mut self, id: WindowId) -> Option<&mut ShellSurface> {
+        self.surfaces
+            .iter_mut()
+            .find(|shell_surface| shell_surface.id == id)
+    }
+
+    /// The window a `wl_surface` belongs to, to route input by the surface it targets.
+    pub(crate) fn window_of(&self, wl_surface: &wl_surface::WlSurface) -> Option<WindowId> {
+        self.surfaces
+            .iter()
+            .find(|shell_surface| &shell_surface.wl_surface == wl_surface)
+            .map(|shell_surface| shell_surface.id)
+    }
+
+    pub(crate) fn create_wgpu_surface(
+        &mut self,
+        connection: &Connection,
+        wl_surface: &wl_surface::WlSurface,
+        surface_config: &SurfaceConfig,
+    ) -> Result<(wgpu::Surface<'static>, SurfaceSettings), WindowError> {
+        let wayland_display_ptr = NonNull::new(connection.backend().display_ptr() as *mut _)
+            .ok_or(WindowError::NullHandle("wl_display"))?;
+        let wayland_display_handle = WaylandDisplayHandle::new(wayland_display_ptr);
+        let raw_display_handle = RawDisplayHandle::Wayland(wayland_display_handle);
+
+        let wayland_surface_ptr = NonNull::new(wl_surface.id().as_ptr() as *mut _)
+            .ok_or(WindowError::NullHandle("wl_surface"))?;
+        let wayland_window_handle = WaylandWindowHandle::new(wayland_surface_ptr);
+        let raw_window_handle = RawWindowHandle::Wayland(wayland_window_handle);
+
+        // ─────────────────────────────────────────────────────────────
+        // `wgpu::Instance`
+        //
+        // The first object created in any WGPU app.
+        // Used to create Adapters and Surfaces.
+        //
+        // It is created along with the first window and reused for the
+        // surfaces of the following ones.
+        // ─────────────────────────────────────────────────────────────
+        let instance = match self.wgpu_state.as_ref() {
+            Some(wgpu_state) => wgpu_state.instance.clone(),
+            None => wgpu::Instance::new(&wgpu::InstanceDescriptor::from_env_or_default()),
+        };
+
+        // ─────────────────────────────────────────────────────────────
+        // `wgpu::Surface`
+        //
+        // The surface is the GPU draw target linked to a native window.
+        //
+        // Creating it requires raw pointers to:
+        //   - The display (`wl_display`)
+        //   - The window (`wl_surface`)
+        //
+        // These are passed through `raw-window-handle`, a cross-platform abstraction
+        // that lets WGPU target Wayland, X11, Windows, etc.
+        //
+        // This block is marked `unsafe` because we're asserting the validity of
+        // raw pointers. If they're null or misused, unknown behavior will occur.
+        // ─────────────────────────────────────────────────────────────
+        let wgpu_surface = unsafe {
+            let surface_target = SurfaceTargetUnsafe::RawHandle {
+                raw_display_handle,
+                raw_window_handle,
+            };
+
+            instance.create_surface_unsafe(surface_target)?
+        };
+
+        let wgpu_state = match self.wgpu_state.take() {
+            Some(wgpu_state) => wgpu_state,
+            None => Self::init_wgpu(instance, &wgpu_surface)?,
+        };
+
+        let capabilities = wgpu_surface.get_capabilities(&wgpu_state.adapter);
+        let downlevel = wgpu_state.adapter.get_downlevel_capabilities().flags;
+        let settings = surface_config.resolve(&capabilities, downlevel);
+
+        self.wgpu_state = Some(wgpu_state);
+
+        Ok((
+            wgpu_surface,
+            settings.ok_or(WindowError::UnsupportedSurface)?,
+        ))
+    }
+
+    fn init_wgpu(
+        instance: wgpu::Instance,
+        wgpu_surface: &wgpu::Surface<'static>,
+    ) -> Result<WgpuState, WindowError> {
+        // ─────────────────────────────────────────────────────────────
+        // GPU Adapter Selection
+        //
+        // An Adapter represents a physical or virtual GPU.
+        //
+        // It exposes:
+        //   - Hardware info (name, limits, features)
+        //   - Backend compatibility (Vulkan, Metal, etc)
+        //   - Methods to request a Device + Queue
+        //
+        // We pass the first window's surface as a compatibility hint since
+        // not all adapters support all surface types. Every window on the
+        // connection then shares the resulting Device and Queue.
+        // ─────────────────────────────────────────────────────────────
+        let adapter_options = wgpu::RequestAdapterOptions {
+            compatible_surface: Some(wgpu_surface),
+            ..Default::default()
+        };
+        let adapter = pollster::block_on(instance.request_adapter(&adapter_options))?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(&Default::default()))?;
+
+        Ok(WgpuState {
+            instance,
+            queue,
+            device,
+            adapter,
+        })
+    }
+}
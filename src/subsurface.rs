@@ -0,0 +1,235 @@
+use wayland_client::{
+    Connection, QueueHandle, delegate_noop,
+    protocol::{wl_subcompositor, wl_subsurface},
+};
+
+use crate::app::WindowEvent;
+use crate::error::WindowError;
+use crate::shell_surface::{Role, ShellSurface, WindowId};
+use crate::state::AppState;
+use crate::surface_config::SurfaceConfig;
+use crate::window::WindowSize;
+
+/// Configures a subsurface opened with [`Window::open_subsurface`](crate::Window::open_subsurface).
+#[derive(Debug, Clone)]
+pub struct SubsurfaceBuilder {
+    pub(crate) size: WindowSize,
+    pub(crate) position: (i32, i32),
+    pub(crate) sync: bool,
+    pub(crate) surface_config: SurfaceConfig,
+}
+
+impl SubsurfaceBuilder {
+    pub fn new(size: WindowSize) -> Self {
+        Self {
+            size,
+            position: (0, 0),
+            sync: true,
+            surface_config: SurfaceConfig::default(),
+        }
+    }
+
+    /// Position of the subsurface's top-left corner, relative to the
+    /// parent's content area.
+    pub fn with_position(mut self, x: i32, y: i32) -> Self {
+        self.position = (x, y);
+        self
+    }
+
+    /// Whether frames wait for the parent's next frame to be shown.
+    /// Enabled by default, see [`Window::set_subsurface_sync`](crate::Window::set_subsurface_sync).
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// See [`SurfaceConfig::with_present_mode`].
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.surface_config = self.surface_config.with_present_mode(present_mode);
+        self
+    }
+
+    /// See [`SurfaceConfig`].
+    pub fn with_surface_config(mut self, surface_config: SurfaceConfig) -> Self {
+        self.surface_config = surface_config;
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Subsurfaces
+//
+// A `wl_subsurface` turns a `wl_surface` into a child of another one.
+// The compositor draws it on top of (or below) its parent, moves it
+// along with it, and clips nothing: it may extend past the parent.
+//
+// Each subsurface has its own `wgpu::Surface` and frames, so a costly
+// layer (e.g, a video or a 3D viewport) and a cheap one (e.g, the UI)
+// can be redrawn independently.
+//
+// Unlike windows, subsurfaces are never configured by the compositor:
+// their size is whatever the client draws, so they are ready right away.
+//
+// Position and stacking changes are double-buffered on the parent:
+// they only take effect with the parent's next commit.
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn open_subsurface(
+        &mut self,
+        parent: WindowId,
+        builder: SubsurfaceBuilder,
+        connection: &Connection,
+        queue_handle: &QueueHandle<AppState>,
+    ) -> Result<WindowId, WindowError> {
+        let subcompositor = self
+            .subcompositor
+            .clone()
+            .ok_or(WindowError::MissingGlobal("wl_subcompositor"))?;
+        let parent_surface = self
+            .shell_surface(parent)
+            .map(|parent| parent.wl_surface.clone())
+            .ok_or(WindowError::InvalidParent)?;
+
+        let (wl_surface, surface, settings) =
+            self.create_surfaces(connection, &builder.surface_config, queue_handle)?;
+
+        let id = self.next_window_id();
+
+        let wl_subsurface =
+            subcompositor.get_subsurface(&wl_surface, &parent_surface, queue_handle, ());
+        if !builder.sync {
+            wl_subsurface.set_desync();
+        }
+
+        let mut shell_surface = ShellSurface::new(
+            id,
+            wl_surface,
+            Role::Subsurface {
+                wl_subsurface,
+                parent,
+                position: builder.position,
+            },
+            surface,
+            settings,
+            builder.size,
+        );
+        self.init_scaling(&mut shell_surface, queue_handle);
+
+        if let Some(wgpu_state) = self.wgpu_state.as_ref() {
+            let size = shell_surface.preferred_size();
+            shell_surface.configure_wgpu(wgpu_state.device(), size.width, size.height);
+            shell_surface.size = Some(size);
+            shell_surface.configured = true;
+        }
+        self.surfaces.push(shell_surface);
+        self.set_subsurface_position(id, builder.position);
+
+        if let Some(size) = self
+            .shell_surface(id)
+            .and_then(|shell_surface| shell_surface.size)
+        {
+            self.push_event(id, WindowEvent::Resized(size));
+        }
+
+        Ok(id)
+    }
+
+    pub(crate) fn subsurface(
+        &self,
+        id: WindowId,
+    ) -> Option<(&wl_subsurface::WlSubsurface, WindowId)> {
+        match &self.shell_surface(id)?.role {
+            Role::Subsurface {
+                wl_subsurface,
+                parent,
+                ..
+            } => Some((wl_subsurface, *parent)),
+            _ => None,
+        }
+    }
+
+    /// The parent has to commit for a change to its subsurfaces to show up.
+    fn redraw_parent(&mut self, parent: WindowId) {
+        if let Some(parent) = self.shell_surface_mut(parent) {
+            parent.needs_redraw = true;
+        }
+    }
+
+    pub(crate) fn set_subsurface_position(&mut self, id: WindowId, (x, y): (i32, i32)) {
+        if let Some(ShellSurface {
+            role: Role::Subsurface { position, .. },
+            ..
+        }) = self.shell_surface_mut(id)
+        {
+            *position = (x, y);
+        }
+
+        let Some((wl_subsurface, parent)) = self.subsurface(id) else {
+            return;
+        };
+
+        // Positions are given in the parent's content area, which starts
+        // after its client-side decorations. They are sent again whenever
+        // the decorations change, see `reposition_subsurfaces`.
+        let insets = self
+            .shell_surface(parent)
+            .map(|parent| parent.content_insets())
+            .unwrap_or_default();
+        wl_subsurface.set_position(x + insets.left, y + insets.top);
+
+        self.redraw_parent(parent);
+    }
+
+    /// Moves the subsurfaces of `parent` back onto its content area after
+    /// its decorations changed (e.g, the border dropped once maximized).
+    pub(crate) fn reposition_subsurfaces(&mut self, parent: WindowId) {
+        let children: Vec<(WindowId, (i32, i32))> = self
+            .surfaces
+            .iter()
+            .filter_map(|shell_surface| match shell_surface.role {
+                Role::Subsurface {
+                    parent: subsurface_parent,
+                    position,
+                    ..
+                } if subsurface_parent == parent => Some((shell_surface.id, position)),
+                _ => None,
+            })
+            .collect();
+
+        for (id, position) in children {
+            self.set_subsurface_position(id, position);
+        }
+    }
+
+    /// Restacks `id` right above or below `sibling`, which is either its
+    /// parent or another subsurface of that parent.
+    pub(crate) fn place_subsurface(&mut self, id: WindowId, sibling: WindowId, above: bool) {
+        let Some((wl_subsurface, parent)) = self.subsurface(id) else {
+            return;
+        };
+        let is_sibling = sibling == parent
+            || self
+                .subsurface(sibling)
+                .is_some_and(|(_, sibling_parent)| sibling_parent == parent);
+        if sibling == id || !is_sibling {
+            return;
+        }
+        let Some(sibling_surface) = self
+            .shell_surface(sibling)
+            .map(|sibling| &sibling.wl_surface)
+        else {
+            return;
+        };
+
+        if above {
+            wl_subsurface.place_above(sibling_surface);
+        } else {
+            wl_subsurface.place_below(sibling_surface);
+        }
+
+        self.redraw_parent(parent);
+    }
+}
+
+delegate_noop!(AppState: ignore wl_subcompositor::WlSubcompositor);
+delegate_noop!(AppState: ignore wl_subsurface::WlSubsurface);
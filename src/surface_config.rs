@@ -0,0 +1,313 @@
+/// How a window's `wgpu::Surface` is configured, see [`WindowBuilder::with_surface_config`](crate::WindowBuilder::with_surface_config).
+///
+/// Each setting is a preference: it is checked against what the adapter
+/// supports for the surface, and the values actually picked are reported
+/// by [`Window::surface_settings`](crate::Window::surface_settings).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceConfig {
+    pub(crate) formats: Vec<wgpu::TextureFormat>,
+    pub(crate) prefer_srgb: bool,
+    pub(crate) present_modes: Vec<wgpu::PresentMode>,
+    pub(crate) alpha_modes: Vec<wgpu::CompositeAlphaMode>,
+    pub(crate) view_formats: Vec<wgpu::TextureFormat>,
+    pub(crate) frame_latency: u32,
+}
+
+impl Default for SurfaceConfig {
+    fn default() -> Self {
+        Self {
+            formats: Vec::new(),
+            prefer_srgb: true,
+            present_modes: vec![wgpu::PresentMode::Mailbox],
+            alpha_modes: Vec::new(),
+            view_formats: Vec::new(),
+            frame_latency: 2,
+        }
+    }
+}
+
+impl SurfaceConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Texture formats to use, most preferred first.
+    ///
+    /// When none is supported (or the list is empty), the first supported
+    /// format matching the sRGB preference is used.
+    pub fn with_formats(mut self, formats: impl Into<Vec<wgpu::TextureFormat>>) -> Self {
+        self.formats = formats.into();
+        self
+    }
+
+    /// Whether to fall back to an sRGB format (`true`, the default) or a
+    /// linear one when none of the preferred formats is supported.
+    pub fn with_srgb(mut self, prefer_srgb: bool) -> Self {
+        self.prefer_srgb = prefer_srgb;
+        self
+    }
+
+    /// Present modes to try in order. Defaults to `Mailbox`.
+    ///
+    /// `Fifo` is always supported, and used when none of them is.
+    pub fn with_present_modes(mut self, present_modes: impl Into<Vec<wgpu::PresentMode>>) -> Self {
+        self.present_modes = present_modes.into();
+        self
+    }
+
+    /// Present mode to use, falling back to `Fifo` when unsupported.
+    ///
+    /// Shorthand for [`SurfaceConfig::with_present_modes`] with a single mode.
+    pub fn with_present_mode(self, present_mode: wgpu::PresentMode) -> Self {
+        self.with_present_modes([present_mode])
+    }
+
+    /// Alpha modes to try in order (e.g, `PreMultiplied` for a translucent
+    /// window). `Auto` is used when none of them is supported.
+    pub fn with_alpha_modes(
+        mut self,
+        alpha_modes: impl Into<Vec<wgpu::CompositeAlphaMode>>,
+    ) -> Self {
+        self.alpha_modes = alpha_modes.into();
+        self
+    }
+
+    /// Extra formats views of the frame can be created with.
+    ///
+    /// Only the sRGB and linear variants of the picked format are allowed,
+    /// other formats are left out. All of them are left out when the adapter
+    /// can't create views in another format than the surface's.
+    pub fn with_view_formats(mut self, view_formats: impl Into<Vec<wgpu::TextureFormat>>) -> Self {
+        self.view_formats = view_formats.into();
+        self
+    }
+
+    /// How many frames can be queued ahead of the one being shown. Defaults to `2`.
+    ///
+    /// Lower values reduce latency, higher ones smooth out frames that take
+    /// longer to draw. Drivers may not honor it exactly.
+    pub fn with_frame_latency(mut self, frame_latency: u32) -> Self {
+        self.frame_latency = frame_latency;
+        self
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Surface Capabilities
+    //
+    // What a surface supports depends on the adapter and the driver:
+    // a format, present mode or alpha mode it doesn't list is a
+    // validation error when configuring it.
+    //
+    // Each preference is matched against `wgpu::SurfaceCapabilities`,
+    // falling back to what is always available. An empty format list
+    // means the adapter can't present to the surface at all.
+    //
+    // View formats are a downlevel capability: adapters without
+    // `SURFACE_VIEW_FORMATS` (e.g, GL, or Vulkan without
+    // `VK_KHR_swapchain_mutable_format`) reject any.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn resolve(
+        &self,
+        capabilities: &wgpu::SurfaceCapabilities,
+        downlevel: wgpu::DownlevelFlags,
+    ) -> Option<SurfaceSettings> {
+        let supported = &capabilities.formats;
+        let format = self
+            .formats
+            .iter()
+            .find(|format| supported.contains(format))
+            .or_else(|| {
+                supported
+                    .iter()
+                    .find(|format| format.is_srgb() == self.prefer_srgb)
+            })
+            .or_else(|| supported.first())
+            .copied()?;
+
+        let present_mode = self
+            .present_modes
+            .iter()
+            .find(|present_mode| capabilities.present_modes.contains(present_mode))
+            .copied()
+            .unwrap_or(wgpu::PresentMode::Fifo);
+
+        let alpha_mode = self
+            .alpha_modes
+            .iter()
+            .find(|alpha_mode| capabilities.alpha_modes.contains(alpha_mode))
+            .copied()
+            .unwrap_or(wgpu::CompositeAlphaMode::Auto);
+
+        let view_formats = if downlevel.contains(wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS) {
+            self.view_formats
+                .iter()
+                .filter(|view_format| {
+                    view_format.remove_srgb_suffix() == format.remove_srgb_suffix()
+                })
+                .copied()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Some(SurfaceSettings {
+            format,
+            present_mode,
+            alpha_mode,
+            view_formats,
+            frame_latency: self.frame_latency,
+        })
+    }
+}
+
+/// Values a window's surface was actually configured with, resolved from
+/// its [`SurfaceConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceSettings {
+    pub format: wgpu::TextureFormat,
+    pub present_mode: wgpu::PresentMode,
+    pub alpha_mode: wgpu::CompositeAlphaMode,
+    pub view_formats: Vec<wgpu::TextureFormat>,
+    pub frame_latency: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(
+        formats: &[wgpu::TextureFormat],
+        present_modes: &[wgpu::PresentMode],
+    ) -> wgpu::SurfaceCapabilities {
+        wgpu::SurfaceCapabilities {
+            formats: formats.to_vec(),
+            present_modes: present_modes.to_vec(),
+            alpha_modes: vec![wgpu::CompositeAlphaMode::Opaque],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn preferred_format_absent_falls_back_to_srgb() {
+        let capabilities = capabilities(
+            &[
+                wgpu::TextureFormat::Bgra8Unorm,
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+            ],
+            &[wgpu::PresentMode::Fifo],
+        );
+
+        let settings = SurfaceConfig::new()
+            .with_formats([wgpu::TextureFormat::Rgba16Float])
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.format, wgpu::TextureFormat::Bgra8UnormSrgb);
+
+        let settings = SurfaceConfig::new()
+            .with_formats([wgpu::TextureFormat::Rgba16Float])
+            .with_srgb(false)
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.format, wgpu::TextureFormat::Bgra8Unorm);
+    }
+
+    #[test]
+    fn preferred_format_wins_when_supported() {
+        let capabilities = capabilities(
+            &[
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+                wgpu::TextureFormat::Rgba16Float,
+            ],
+            &[wgpu::PresentMode::Fifo],
+        );
+
+        let settings = SurfaceConfig::new()
+            .with_formats([
+                wgpu::TextureFormat::Rgb10a2Unorm,
+                wgpu::TextureFormat::Rgba16Float,
+            ])
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.format, wgpu::TextureFormat::Rgba16Float);
+    }
+
+    #[test]
+    fn no_supported_format_is_unsupported() {
+        let capabilities = capabilities(&[], &[]);
+
+        assert_eq!(
+            SurfaceConfig::new().resolve(&capabilities, wgpu::DownlevelFlags::all()),
+            None
+        );
+    }
+
+    #[test]
+    fn mailbox_falls_back_to_fifo() {
+        let capabilities = capabilities(
+            &[wgpu::TextureFormat::Bgra8UnormSrgb],
+            &[wgpu::PresentMode::Fifo, wgpu::PresentMode::Immediate],
+        );
+
+        let settings = SurfaceConfig::new()
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.present_mode, wgpu::PresentMode::Fifo);
+
+        let settings = SurfaceConfig::new()
+            .with_present_modes([wgpu::PresentMode::Mailbox, wgpu::PresentMode::Immediate])
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.present_mode, wgpu::PresentMode::Immediate);
+    }
+
+    #[test]
+    fn unsupported_alpha_mode_falls_back_to_auto() {
+        let capabilities = capabilities(
+            &[wgpu::TextureFormat::Bgra8UnormSrgb],
+            &[wgpu::PresentMode::Fifo],
+        );
+
+        let settings = SurfaceConfig::new()
+            .with_alpha_modes([wgpu::CompositeAlphaMode::PreMultiplied])
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(settings.alpha_mode, wgpu::CompositeAlphaMode::Auto);
+    }
+
+    #[test]
+    fn view_formats_outside_the_srgb_pair_are_dropped() {
+        let capabilities = capabilities(
+            &[wgpu::TextureFormat::Bgra8Unorm],
+            &[wgpu::PresentMode::Fifo],
+        );
+
+        let settings = SurfaceConfig::new()
+            .with_view_formats([
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+            ])
+            .resolve(&capabilities, wgpu::DownlevelFlags::all())
+            .unwrap();
+        assert_eq!(
+            settings.view_formats,
+            vec![wgpu::TextureFormat::Bgra8UnormSrgb]
+        );
+    }
+
+    #[test]
+    fn view_formats_need_the_downlevel_capability() {
+        let capabilities = capabilities(
+            &[wgpu::TextureFormat::Bgra8Unorm],
+            &[wgpu::PresentMode::Fifo],
+        );
+
+        let settings = SurfaceConfig::new()
+            .with_view_formats([wgpu::TextureFormat::Bgra8UnormSrgb])
+            .resolve(
+                &capabilities,
+                wgpu::DownlevelFlags::all() - wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS,
+            )
+            .unwrap();
+        assert!(settings.view_formats.is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use wayland_client::{QueueHandle, delegate_noop};
+use wayland_protocols::wp::tearing_control::v1::client::{
+    wp_tearing_control_manager_v1::WpTearingControlManagerV1,
+    wp_tearing_control_v1::{PresentationHint, WpTearingControlV1},
+};
+
+use crate::shell_surface::ShellSurface;
+use crate::state::AppState;
+
+// ─────────────────────────────────────────────────────────────
+// Tearing Control
+//
+// Compositors normally wait for the vertical blank to show a new frame,
+// so it never tears. Waiting adds up to a refresh interval of latency,
+// which games may rather trade for tearing.
+//
+// Two things are needed for a frame to reach the screen right away:
+//
+//   1. `wgpu::PresentMode::Immediate`, so `wgpu` itself doesn't queue
+//      frames. It is tried before the window's own present modes, which
+//      are used instead when the driver doesn't support it.
+//   2. `wp_tearing_control_v1` with the `async` hint, so the compositor
+//      flips to the frame as soon as it arrives instead of at the next
+//      vertical blank. Without the global, only the first part applies.
+//
+// The hint is only sent when `Immediate` was picked: with any other
+// present mode, frames wait for the vertical blank anyway.
+//
+// Only one tearing control object may exist per surface, a second one is
+// a protocol error that ends the connection. Vulkan drivers (e.g, Mesa
+// since 24.1) create their own for `Immediate`, so the hint is left to
+// them on the Vulkan backend and only sent here on the others.
+//
+// The hint is double-buffered: it takes effect with the first frame.
+// A compositor may still ignore it (e.g, when the window isn't fullscreen).
+// ─────────────────────────────────────────────────────────────
+impl AppState {
+    pub(crate) fn init_tearing(
+        &self,
+        shell_surface: &mut ShellSurface,
+        queue_handle: &QueueHandle<AppState>,
+    ) {
+        let (Some(manager), Some(wgpu_state)) = (
+            self.tearing_control_manager.as_ref(),
+            self.wgpu_state.as_ref(),
+        ) else {
+            return;
+        };
+
+        let can_tear = shell_surface.settings.present_mode == wgpu::PresentMode::Immediate;
+        let driver_hints = wgpu_state.adapter().get_info().backend == wgpu::Backend::Vulkan;
+        if can_tear && !driver_hints {
+            let tearing_control =
+                manager.get_tearing_control(&shell_surface.wl_surface, queue_handle, ());
+            tearing_control.set_presentation_hint(PresentationHint::Async);
+            shell_surface.tearing_control = Some(tearing_control);
+        }
+    }
+}
+
+delegate_noop!(AppState: ignore WpTearingControlManagerV1);
+delegate_noop!(AppState: ignore WpTearingControlV1);
@@ -0,0 +1,1016 @@
+use std::io::ErrorKind;
+use std::time::Instant;
+
+use rustix::event::{PollFd, PollFlags, poll};
+use rustix::io::Errno;
+use wayland_client::{Connection, DispatchError, EventQueue, QueueHandle, backend::WaylandError};
+use wayland_protocols::xdg::shell::client::xdg_toplevel;
+
+use crate::app::{App, WindowEvent};
+use crate::csd::DecorationResponse;
+use crate::decoration::DecorationMode;
+use crate::error::WindowError;
+use crate::layer_shell::LayerSurfaceBuilder;
+use crate::output::Output;
+use crate::popup::PopupBuilder;
+use crate::presentation::FrameStats;
+use crate::shell_surface::{Role, ShellSurface, WindowId};
+use crate::state::{AppState, WgpuState};
+use crate::subsurface::SubsurfaceBuilder;
+use crate::surface_config::{SurfaceConfig, SurfaceSettings};
+
+/// Size of a window's content area, in surface-local coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Default for WindowSize {
+    fn default() -> Self {
+        Self {
+            width: 320,
+            height: 320,
+        }
+    }
+}
+
+/// Edge or corner of the window grabbed for an interactive resize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResizeEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl From<ResizeEdge> for xdg_toplevel::ResizeEdge {
+    fn from(edge: ResizeEdge) -> Self {
+        match edge {
+            ResizeEdge::Top => Self::Top,
+            ResizeEdge::Bottom => Self::Bottom,
+            ResizeEdge::Left => Self::Left,
+            ResizeEdge::Right => Self::Right,
+            ResizeEdge::TopLeft => Self::TopLeft,
+            ResizeEdge::TopRight => Self::TopRight,
+            ResizeEdge::BottomLeft => Self::BottomLeft,
+            ResizeEdge::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+/// States of the window, as last confirmed by the compositor.
+///
+/// Only changes when the compositor configures the window, so a state
+/// requested by the app shows up here once it was actually applied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowState {
+    pub maximized: bool,
+    pub fullscreen: bool,
+    /// An interactive resize is in progress.
+    pub resizing: bool,
+    /// The window has focus. Decorations are usually drawn differently without it.
+    pub activated: bool,
+    /// Each edge is placed against something (e.g, another tiled window or the
+    /// screen edge), so shadows or rounded corners there should be dropped.
+    pub tiled_left: bool,
+    pub tiled_right: bool,
+    pub tiled_top: bool,
+    pub tiled_bottom: bool,
+    /// The window is not visible at all (e.g, minimized or fully occluded).
+    /// Rendering can be paused until this is cleared.
+    pub suspended: bool,
+}
+
+impl WindowState {
+    /// `true` when any edge is tiled.
+    pub fn is_tiled(&self) -> bool {
+        self.tiled_left || self.tiled_right || self.tiled_top || self.tiled_bottom
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Toplevel States
+    //
+    // `xdg_toplevel.configure` carries the states as a `wl_array` of
+    // native-endian `u32` enum values. Values this crate doesn't know
+    // (from newer protocol versions) are skipped.
+    //
+    // Tiled states are only sent from version 2 and `suspended` from
+    // version 6, older compositors just leave them out.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn from_wire(states: &[u8]) -> Self {
+        let mut window_state = Self::default();
+
+        let values = states
+            .chunks_exact(4)
+            .filter_map(|chunk| chunk.try_into().ok())
+            .map(u32::from_ne_bytes)
+            .filter_map(|value| xdg_toplevel::State::try_from(value).ok());
+
+        for value in values {
+            match value {
+                xdg_toplevel::State::Maximized => window_state.maximized = true,
+                xdg_toplevel::State::Fullscreen => window_state.fullscreen = true,
+                xdg_toplevel::State::Resizing => window_state.resizing = true,
+                xdg_toplevel::State::Activated => window_state.activated = true,
+                xdg_toplevel::State::TiledLeft => window_state.tiled_left = true,
+                xdg_toplevel::State::TiledRight => window_state.tiled_right = true,
+                xdg_toplevel::State::TiledTop => window_state.tiled_top = true,
+                xdg_toplevel::State::TiledBottom => window_state.tiled_bottom = true,
+                xdg_toplevel::State::Suspended => window_state.suspended = true,
+                _ => {}
+            }
+        }
+
+        window_state
+    }
+}
+
+/// Window management features the compositor supports.
+///
+/// Requests for unsupported features are silently ignored by the compositor,
+/// so UI offering them (e.g, a maximize button) should be hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WmCapabilities {
+    /// [`Window::show_window_menu`] opens a menu.
+    pub window_menu: bool,
+    pub maximize: bool,
+    pub fullscreen: bool,
+    pub minimize: bool,
+}
+
+impl Default for WmCapabilities {
+    /// Everything is supported, which is what compositors that predate
+    /// `wm_capabilities` are assumed to do.
+    fn default() -> Self {
+        Self {
+            window_menu: true,
+            maximize: true,
+            fullscreen: true,
+            minimize: true,
+        }
+    }
+}
+
+impl WmCapabilities {
+    // ─────────────────────────────────────────────────────────────
+    // `wm_capabilities`
+    //
+    // Sent since `xdg_toplevel` version 5, as a `wl_array` of native-endian
+    // `u32` values listing what IS supported. An empty array means
+    // nothing is. Compositors on older versions never send it, in which
+    // case everything is assumed to be supported.
+    // ─────────────────────────────────────────────────────────────
+    pub(crate) fn from_wire(capabilities: &[u8]) -> Self {
+        let mut wm_capabilities = Self {
+            window_menu: false,
+            maximize: false,
+            fullscreen: false,
+            minimize: false,
+        };
+
+        let values = capabilities
+            .chunks_exact(4)
+            .filter_map(|chunk| chunk.try_into().ok())
+            .map(u32::from_ne_bytes)
+            .filter_map(|value| xdg_toplevel::WmCapabilities::try_from(value).ok());
+
+        for value in values {
+            match value {
+                xdg_toplevel::WmCapabilities::WindowMenu => wm_capabilities.window_menu = true,
+                xdg_toplevel::WmCapabilities::Maximize => wm_capabilities.maximize = true,
+                xdg_toplevel::WmCapabilities::Fullscreen => wm_capabilities.fullscreen = true,
+                xdg_toplevel::WmCapabilities::Minimize => wm_capabilities.minimize = true,
+                _ => {}
+            }
+        }
+
+        wm_capabilities
+    }
+}
+
+/// Configures and opens a [`Window`].
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    pub(crate) title: String,
+    pub(crate) app_id: Option<String>,
+    pub(crate) size: WindowSize,
+    pub(crate) min_size: Option<WindowSize>,
+    pub(crate) max_size: Option<WindowSize>,
+    pub(crate) surface_config: SurfaceConfig,
+    pub(crate) tearing: bool,
+    pub(crate) decoration_mode: DecorationMode,
+    pub(crate) client_side_decorations: bool,
+}
+
+impl Default for WindowBuilder {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            app_id: None,
+            size: WindowSize::default(),
+            min_size: None,
+            max_size: None,
+            surface_config: SurfaceConfig::default(),
+            tearing: false,
+            decoration_mode: DecorationMode::ServerSide,
+            client_side_decorations: false,
+        }
+    }
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Title shown by the compositor (task bars, window switchers, decorations).
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Application identifier, usually matching the `.desktop` file name.
+    pub fn with_app_id(mut self, app_id: impl Into<String>) -> Self {
+        self.app_id = Some(app_id.into());
+        self
+    }
+
+    /// Size used when the compositor lets the client pick its own.
+    ///
+    /// It is shrunk to fit the bounds reported by the compositor (usually the
+    /// output's usable area) and kept within the min/max size.
+    pub fn with_size(mut self, size: WindowSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Smallest size the window can be resized to, client-side decorations included.
+    pub fn with_min_size(mut self, size: WindowSize) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    /// Largest size the window can be resized to, client-side decorations included.
+    ///
+    /// A dimension of `0` leaves that axis unconstrained.
+    pub fn with_max_size(mut self, size: WindowSize) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// See [`SurfaceConfig::with_present_mode`].
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.surface_config = self.surface_config.with_present_mode(present_mode);
+        self
+    }
+
+    /// See [`SurfaceConfig`].
+    pub fn with_surface_config(mut self, surface_config: SurfaceConfig) -> Self {
+        self.surface_config = surface_config;
+        self
+    }
+
+    /// Shows frames as soon as they are ready, even if they tear, for the
+    /// lowest latency (e.g, in games). Disabled by default.
+    ///
+    /// Tries `wgpu::PresentMode::Immediate` before the configured present
+    /// modes. When it is picked, the compositor is also asked for asynchronous
+    /// page flips through `wp_tearing_control_v1`: by the driver on the Vulkan
+    /// backend, by this crate on the others.
+    pub fn with_tearing(mut self, enabled: bool) -> Self {
+        self.tearing = enabled;
+        self
+    }
+
+    /// Decoration mode to ask the compositor for. Defaults to server-side.
+    ///
+    /// The compositor may pick a different one, see [`Window::decoration_mode`].
+    pub fn with_decoration_mode(mut self, decoration_mode: DecorationMode) -> Self {
+        self.decoration_mode = decoration_mode;
+        self
+    }
+
+    /// Draws a built-in title bar, buttons and resize borders whenever the
+    /// compositor leaves decorating to the client. Disabled by default.
+    ///
+    /// The decorations take space from the configured size: [`Window::size`],
+    /// [`App::resize`](crate::App::resize) and input coordinates all refer to
+    /// the content area inside them.
+    pub fn with_client_side_decorations(mut self, enabled: bool) -> Self {
+        self.client_side_decorations = enabled;
+        self
+    }
+
+    /// Connects to the compositor, creates the `xdg_toplevel` and sets up `wgpu`.
+    pub fn build(self) -> Result<Window, WindowError> {
+        Window::connect(|state, connection, queue_handle| {
+            state.open_toplevel(self, connection, queue_handle)
+        })
+    }
+}
+
+/// A Wayland `xdg_toplevel` window with a `wgpu` surface attached.
+///
+/// It also owns the connection and the event loop, so more windows sharing
+/// the same GPU device can be opened from it with [`Window::open_window`].
+/// Methods act on the window identified by [`Window::id`].
+pub struct Window {
+    connection: Connection,
+    event_queue: EventQueue<AppState>,
+    pub(crate) state: AppState,
+    pub(crate) current: WindowId,
+}
+
+impl Window {
+    /// Connects to the compositor and binds its globals, then opens the
+    /// first window through `open`.
+    pub(crate) fn connect(
+        open: impl FnOnce(
+            &mut AppState,
+            &Connection,
+            &QueueHandle<AppState>,
+        ) -> Result<WindowId, WindowError>,
+    ) -> Result<Window, WindowError> {
+        // ─────────────────────────────────────────────────────────────
+        // Attempts to connect to the compositor based on environment config.
+        // ─────────────────────────────────────────────────────────────
+        let connection = Connection::connect_to_env()?;
+
+        // ─────────────────────────────────────────────────────────────
+        // `wl_display`
+        //
+        // Root object in any Wayland program.
+        // All Wayland objects are created from the display.
+        // ─────────────────────────────────────────────────────────────
+        let display = connection.display();
+
+        // ─────────────────────────────────────────────────────────────
+        // Event Queue
+        //
+        // Event queues process all incoming Wayland events.
+        //
+        // The handle is required to associate newly created objects
+        // (like the registry) with this queue.
+        // ─────────────────────────────────────────────────────────────
+        let mut event_queue = connection.new_event_queue();
+        let queue_handle = event_queue.handle();
+
+        // ─────────────────────────────────────────────────────────────
+        // `wl_registry`
+        //
+        // Used to list and bind to compositor-exposed globals.
+        //
+        // When created, it emits a event for each available interface.
+        // Globals can appear or disappear dynamically (e.g, on hotplug or reconfig).
+        //
+        // Binding is done using the event queue handle, giving the client a local object
+        // to send requests and receive events.
+        //
+        // The `Dispatch<wl_registry, _>` impl for AppState handles each global as it's announced.
+        // ─────────────────────────────────────────────────────────────
+        display.get_registry(&queue_handle, ());
+
+        let mut state = AppState::new();
+
+        // ─────────────────────────────────────────────────────────────
+        // Initial Roundtrip
+        //
+        // A roundtrip blocks until the compositor has processed every request
+        // sent so far, which guarantees that all registry events have been
+        // dispatched and the globals bound before creating the window.
+        //
+        // This is a one-time setup dispatch.
+        // ─────────────────────────────────────────────────────────────
+        event_queue.roundtrip(&mut state)?;
+
+        // ─────────────────────────────────────────────────────────────
+        // Required Globals
+        //
+        // Every global is announced in response to `get_registry`, so
+        // anything missing after the roundtrip is not supported by the
+        // compositor at all. `wl_compositor` is needed by every window,
+        // the role-specific ones (e.g, `xdg_wm_base`) are checked when
+        // opening a window of that kind.
+        // ─────────────────────────────────────────────────────────────
+        if state.compositor.is_none() {
+            return Err(WindowError::MissingGlobal("wl_compositor"));
+        }
+
+        let id = open(&mut state, &connection, &queue_handle)?;
+
+        Ok(Window {
+            connection,
+            event_queue,
+            state,
+            current: id,
+        })
+    }
+
+    /// `false` once every window has been closed, or after [`Window::exit`].
+    pub fn is_running(&self) -> bool {
+        self.state.running
+    }
+
+    /// The window the current callback is about, and the one other methods act on.
+    pub fn id(&self) -> WindowId {
+        self.current
+    }
+
+    /// Every window still open on this connection.
+    pub fn windows(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.state
+            .surfaces
+            .iter()
+            .map(|shell_surface| shell_surface.id)
+    }
+
+    /// Makes `id` the window other methods act on, until the next callback.
+    ///
+    /// Returns `false` if that window is closed.
+    pub fn select(&mut self, id: WindowId) -> bool {
+        self.current = id;
+        self.state.shell_surface(id).is_some()
+    }
+
+    /// Opens another window sharing this one's connection and GPU device.
+    ///
+    /// Its events go through the same [`App`], with [`Window::id`] telling
+    /// the windows apart.
+    pub fn open_window(&mut self, builder: WindowBuilder) -> Result<WindowId, WindowError> {
+        let queue_handle = self.event_queue.handle();
+        self.state
+            .open_toplevel(builder, &self.connection, &queue_handle)
+    }
+
+    /// Opens a layer surface (panel, overlay, wallpaper) sharing this window's
+    /// connection and GPU device.
+    pub fn open_layer_surface(
+        &mut self,
+        builder: LayerSurfaceBuilder,
+    ) -> Result<WindowId, WindowError> {
+        let queue_handle = self.event_queue.handle();
+        self.state
+            .open_layer_surface(builder, &self.connection, &queue_handle)
+    }
+
+    /// Opens a popup (menu, tooltip) on the current window, or on the current popup
+    /// to nest menus.
+    ///
+    /// It is drawn through the same [`App`] as any window, and closed with
+    /// [`Window::close`] after selecting it. Closing a window closes its popups too.
+    pub fn open_popup(&mut self, builder: PopupBuilder) -> Result<WindowId, WindowError> {
+        let queue_handle = self.event_queue.handle();
+        self.state
+            .open_popup(self.current, builder, &self.connection, &queue_handle)
+    }
+
+    /// Asks the compositor to place the current popup again with new rules.
+    ///
+    /// The size is applied too. Does nothing on windows that aren't popups,
+    /// or on compositors older than `xdg_wm_base` version 3.
+    pub fn reposition_popup(&mut self, builder: &PopupBuilder) {
+        let queue_handle = self.event_queue.handle();
+        self.state
+            .reposition_popup(self.current, builder, &queue_handle);
+    }
+
+    /// The window the current popup was opened on, `None` for other windows.
+    pub fn parent(&self) -> Option<WindowId> {
+        self.shell_surface()?.parent()
+    }
+
+    /// Position the compositor placed the current popup at, relative to its
+    /// parent's content area (like the anchor rect of [`PopupBuilder`]).
+    pub fn popup_position(&self) -> Option<(i32, i32)> {
+        match self.shell_surface()?.role {
+            Role::Popup { position, .. } => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Opens a subsurface on the current window: a child surface drawn with
+    /// its own frames, on top of the window by default.
+    ///
+    /// It is drawn through the same [`App`](crate::App) as any window, and closed
+    /// with [`Window::close`] after selecting it. Closing a window closes its
+    /// subsurfaces too.
+    pub fn open_subsurface(&mut self, builder: SubsurfaceBuilder) -> Result<WindowId, WindowError> {
+        let queue_handle = self.event_queue.handle();
+        self.state
+            .open_subsurface(self.current, builder, &self.connection, &queue_handle)
+    }
+
+    /// Moves the current subsurface, relative to its parent's content area.
+    pub fn set_subsurface_position(&mut self, x: i32, y: i32) {
+        self.state.set_subsurface_position(self.current, (x, y));
+    }
+
+    /// Stacks the current subsurface right above `sibling`: its parent, or
+    /// another subsurface of the same parent.
+    pub fn place_above(&mut self, sibling: WindowId) {
+        self.state.place_subsurface(self.current, sibling, true);
+    }
+
+    /// Stacks the current subsurface right below `sibling`: its parent, or
+    /// another subsurface of the same parent.
+    ///
+    /// Below the parent, it only shows through the parent's transparent pixels.
+    pub fn place_below(&mut self, sibling: WindowId) {
+        self.state.place_subsurface(self.current, sibling, false);
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Synchronization
+    //
+    //   - sync   → the subsurface's frames are held by the compositor and
+    //              shown along with the parent's next frame, so both always
+    //              match (e.g, while resizing).
+    //   - desync → frames are shown as soon as they are presented, without
+    //              waiting for the parent. Needed for content updated at its
+    //              own pace under a parent that rarely redraws.
+    //
+    // A desync subsurface still behaves as sync while its parent is sync.
+    // ─────────────────────────────────────────────────────────────
+
+    /// Switches the current subsurface between sync and desync mode.
+    pub fn set_subsurface_sync(&mut self, sync: bool) {
+        if let Some((wl_subsurface, _)) = self.state.subsurface(self.current) {
+            if sync {
+                wl_subsurface.set_sync();
+            } else {
+                wl_subsurface.set_desync();
+            }
+        }
+    }
+
+    /// Resizes the current subsurface.
+    ///
+    /// Subsurfaces are never resized by the compositor, so this is the only
+    /// way their size changes. [`App::resize`](crate::App::resize) follows.
+    pub fn resize_subsurface(&mut self, size: WindowSize) {
+        let Some(wgpu_state) = self.state.wgpu_state.as_ref() else {
+            return;
+        };
+        let Some(shell_surface) = self
+            .state
+            .surfaces
+            .iter_mut()
+            .find(|shell_surface| shell_surface.id == self.current)
+            .filter(|shell_surface| matches!(shell_surface.role, Role::Subsurface { .. }))
+        else {
+            return;
+        };
+
+        let size = WindowSize {
+            width: size.width.max(1),
+            height: size.height.max(1),
+        };
+        if shell_surface.size == Some(size) {
+            return;
+        }
+
+        shell_surface.configure_wgpu(wgpu_state.device(), size.width, size.height);
+        shell_surface.size = Some(size);
+        shell_surface.needs_redraw = true;
+        self.state
+            .push_event(self.current, WindowEvent::Resized(size));
+    }
+
+    pub(crate) fn shell_surface(&self) -> Option<&ShellSurface> {
+        self.state.shell_surface(self.current)
+    }
+
+    fn xdg_toplevel(&self) -> Option<&xdg_toplevel::XdgToplevel> {
+        self.shell_surface()?.xdg_toplevel()
+    }
+
+    /// `true` once the first `xdg_surface.configure` has been acknowledged.
+    ///
+    /// Nothing should be presented before this point.
+    pub fn is_configured(&self) -> bool {
+        self.shell_surface()
+            .is_some_and(|shell_surface| shell_surface.configured)
+    }
+
+    /// Current size of the window's content area in logical pixels, if it has been configured.
+    pub fn size(&self) -> Option<WindowSize> {
+        self.shell_surface()?.content_size()
+    }
+
+    /// Scale the window is drawn at, from logical sizes to physical pixels.
+    ///
+    /// Follows the outputs the window is on: their fractional scale when the
+    /// compositor supports it, their integer scale otherwise.
+    pub fn scale(&self) -> f64 {
+        self.shell_surface()
+            .map_or(1.0, |shell_surface| shell_surface.scale)
+    }
+
+    /// Timing of the window's last frames, from presentation feedback.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.shell_surface()
+            .map(|shell_surface| FrameStats::from_history(&shell_surface.frame_history))
+            .unwrap_or_default()
+    }
+
+    /// States last confirmed by the compositor (maximized, activated, ...).
+    pub fn state(&self) -> WindowState {
+        self.shell_surface()
+            .map(|shell_surface| shell_surface.window_state)
+            .unwrap_or_default()
+    }
+
+    /// Window management features the compositor announced support for.
+    pub fn wm_capabilities(&self) -> WmCapabilities {
+        self.shell_surface()
+            .map(|shell_surface| shell_surface.wm_capabilities)
+            .unwrap_or_default()
+    }
+
+    /// Decoration mode granted by the compositor.
+    ///
+    /// When this is [`DecorationMode::ClientSide`], the window has no title bar
+    /// or borders unless the app draws them, or enabled
+    /// [`WindowBuilder::with_client_side_decorations`].
+    pub fn decoration_mode(&self) -> DecorationMode {
+        self.shell_surface()
+            .map(|shell_surface| shell_surface.decoration_mode)
+            .unwrap_or_default()
+    }
+
+    /// The window's own `wgpu` surface, `None` once it has been closed.
+    pub fn surface(&self) -> Option<&wgpu::Surface<'static>> {
+        self.shell_surface()
+            .map(|shell_surface| &shell_surface.surface)
+    }
+
+    /// Texture format the window's surface is configured with.
+    pub fn format(&self) -> Option<wgpu::TextureFormat> {
+        self.shell_surface()
+            .map(|shell_surface| shell_surface.settings.format)
+    }
+
+    /// Settings picked for the window's surface from its [`SurfaceConfig`].
+    pub fn surface_settings(&self) -> Option<&SurfaceSettings> {
+        self.shell_surface()
+            .map(|shell_surface| &shell_surface.settings)
+    }
+
+    pub fn wgpu_state(&self) -> &WgpuState {
+        // `WindowBuilder::build` only returns a `Window` once WGPU is set up.
+        self.state
+            .wgpu_state
+            .as_ref()
+            .expect("WgpuState is always initialized by WindowBuilder::build")
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Interactive Move / Resize
+    //
+    // Without server-side decorations, the client draws its own title bar
+    // and borders. When the user presses on them, the client hands the
+    // pointer grab over to the compositor, which then moves or resizes the
+    // window until the button is released.
+    //
+    // These requests must be sent in response to a button press (or touch
+    // down), identified by the serial of that event. Compositors ignore
+    // them if the serial is stale or the button was already released.
+    // ─────────────────────────────────────────────────────────────
+
+    /// Starts an interactive move, in response to the input event with `serial`.
+    pub fn start_move(&self, serial: u32) {
+        if let (Some(toplevel), Some(seat)) = (self.xdg_toplevel(), self.state.input_seat()) {
+            toplevel._move(seat, serial);
+        }
+    }
+
+    /// Starts an interactive resize from `edge`, in response to the input event with `serial`.
+    pub fn start_resize(&self, serial: u32, edge: ResizeEdge) {
+        if let (Some(toplevel), Some(seat)) = (self.xdg_toplevel(), self.state.input_seat()) {
+            toplevel.resize(seat, serial, edge.into());
+        }
+    }
+
+    /// Shows the compositor's window menu at (`x`, `y`), relative to the window.
+    pub fn show_window_menu(&self, serial: u32, x: i32, y: i32) {
+        if let (Some(toplevel), Some(seat)) = (self.xdg_toplevel(), self.state.input_seat()) {
+            toplevel.show_window_menu(seat, serial, x, y);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Window States
+    //
+    // These are requests, not commands: the compositor decides whether
+    // and when to apply them, and may refuse entirely (e.g, a fixed-size
+    // window can't be maximized on some compositors).
+    //
+    // Nothing changes locally when sending them. The outcome arrives as
+    // a configure, which updates `Window::state` and calls
+    // `App::state_changed` (and `App::resize` if the size changed too).
+    //
+    // Minimizing is the exception: `xdg_toplevel` has no minimized state,
+    // so the only visible effect may be `WindowState::suspended`.
+    // ─────────────────────────────────────────────────────────────
+
+    /// Asks to make the window fullscreen, on `output` or one the compositor picks.
+    pub fn set_fullscreen(&self, output: Option<&Output>) {
+        if let Some(toplevel) = self.xdg_toplevel() {
+            toplevel.set_fullscreen(output.map(|output| &output.wl_output));
+        }
+    }
+
+    pub fn unset_fullscreen(&self) {
+        if let Some(toplevel) = self.xdg_toplevel() {
+            toplevel.unset_fullscreen();
+        }
+    }
+
+    pub fn set_maximized(&self) {
+        if let Some(toplevel) = self.xdg_toplevel() {
+            toplevel.set_maximized();
+        }
+    }
+
+    pub fn unset_maximized(&self) {
+        if let Some(toplevel) = self.xdg_toplevel() {
+            toplevel.unset_maximized();
+        }
+    }
+
+    pub fn set_minimized(&self) {
+        if let Some(toplevel) = self.xdg_toplevel() {
+            toplevel.set_minimized();
+        }
+    }
+
+    /// Outputs currently advertised by the compositor.
+    pub fn outputs(&self) -> &[Output] {
+        &self.state.outputs
+    }
+
+    /// Outputs the window is currently shown on, at least partially.
+    pub fn current_outputs(&self) -> impl Iterator<Item = &Output> + '_ {
+        let entered = self
+            .shell_surface()
+            .map(|shell_surface| shell_surface.outputs.as_slice())
+            .unwrap_or_default();
+
+        self.state
+            .outputs
+            .iter()
+            .filter(move |output| entered.contains(&output.wl_output))
+    }
+
+    /// Closes the window. The event loop stops once the last one is closed.
+    pub fn close(&mut self) {
+        self.state.close_surface(self.current);
+    }
+
+    /// Stops the event loop after the current iteration, closing every window.
+    pub fn exit(&mut self) {
+        self.state.running = false;
+    }
+
+    // ─────────────────────────────────────────────────────────────
+    // Dispatching
+    //
+    // Waits for new Wayland events and processes them.
+    //
+    // This is what `blocking_dispatch()` does, split into its steps
+    // so the wait can be bounded by a deadline:
+    //
+    //   1. Dispatch events that are already queued, without blocking.
+    //   2. Flush our outgoing requests to the compositor.
+    //   3. `prepare_read()` and poll the socket until it is readable
+    //      or the deadline passes.
+    //   4. Read whatever arrived and dispatch it to AppState.
+    //
+    // `prepare_read()` returns `None` when events were queued in between,
+    // in which case we simply dispatch those instead of waiting.
+    // ─────────────────────────────────────────────────────────────
+    fn dispatch_until(&mut self, deadline: Option<Instant>) -> Result<(), WindowError> {
+        if self.event_queue.dispatch_pending(&mut self.state)? > 0 {
+            return Ok(());
+        }
+
+        self.event_queue.flush().map_err(DispatchError::Backend)?;
+
+        if let Some(guard) = self.event_queue.prepare_read() {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    remaining.as_millis().min(i32::MAX as u128) as i32
+                }
+                None => -1,
+            };
+
+            let fd = guard.connection_fd();
+            let mut fds = [PollFd::new(&fd, PollFlags::IN)];
+            let readable = match poll(&mut fds, timeout) {
+                Ok(ready) => ready > 0,
+                Err(Errno::INTR) => false,
+                Err(error) => {
+                    return Err(DispatchError::Backend(WaylandError::Io(error.into())).into());
+                }
+            };
+
+            if readable {
+                match guard.read() {
+                    Ok(_) => {}
+                    Err(WaylandError::Io(error)) if error.kind() == ErrorKind::WouldBlock => {}
+                    Err(error) => return Err(DispatchError::Backend(error).into()),
+                }
+            }
+        }
+
+        self.event_queue.dispatch_pending(&mut self.state)?;
+
+        Ok(())
+    }
+
+    /// Closes the window unless `app` objects.
+    fn request_close(&mut self, app: &mut impl App) {
+        if app.close_requested(self) {
+            self.close();
+        }
+    }
+
+    /// Runs the event loop, driving `app` until every window is closed.
+    pub fn run(mut self, app: &mut impl App) -> Result<(), WindowError> {
+        app.init(&mut self);
+
+        while self.state.running {
+            // A frame callback from the compositor is one of the events waited on,
+            // so this wakes up once per compositor frame while a window is visible.
+            // A held key wakes it up early to synthesize the repeat.
+            self.dispatch_until(self.state.next_key_repeat())?;
+            self.state.repeat_keys(Instant::now());
+
+            for (id, event) in std::mem::take(&mut self.state.events) {
+                // The window may have been closed by an earlier event.
+                if !self.select(id) {
+                    continue;
+                }
+
+                match event {
+                    WindowEvent::Resized(size) => app.resize(&mut self, size),
+                    WindowEvent::ScaleChanged(scale) => app.scale_changed(&mut self, scale),
+                    WindowEvent::StateChanged(state) => app.state_changed(&mut self, state),
+                    WindowEvent::Input(event) => match self.handle_decoration_input(event) {
+                        DecorationResponse::Forward(event) => app.input(&mut self, event),
+                        DecorationResponse::Consumed => {}
+                        DecorationResponse::CloseRequested => self.request_close(app),
+                    },
+                    WindowEvent::CloseRequested => self.request_close(app),
+                    WindowEvent::PopupRepositioned(x, y) => app.popup_repositioned(&mut self, x, y),
+                    WindowEvent::PopupDone => {
+                        app.popup_done(&mut self);
+                        self.close();
+                    }
+                    WindowEvent::Closed => {
+                        app.closed(&mut self);
+                        self.close();
+                    }
+                    WindowEvent::FramePresented(feedback) => {
+                        app.frame_presented(&mut self, feedback)
+                    }
+                }
+            }
+
+            // ─────────────────────────────────────────────────────────────
+            // Rendering
+            //
+            // Once a window's surface has been configured and the compositor
+            // signaled it is ready for a new frame, we draw to it.
+            // Each window is paced by its own frame callbacks.
+            // ─────────────────────────────────────────────────────────────
+            let ready: Vec<WindowId> = self
+                .state
+                .surfaces
+                .iter()
+                .filter(|shell_surface| shell_surface.should_draw())
+                .map(|shell_surface| shell_surface.id)
+                .collect();
+
+            for id in ready {
+                if !self.state.running || !self.select(id) {
+                    continue;
+                }
+
+                app.update(&mut self);
+
+                let queue_handle = self.event_queue.handle();
+                self.state.draw(id, &queue_handle, app)?;
+            }
+
+            if self.state.surfaces.is_empty() {
+                self.state.running = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wire(values: &[u32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn window_state_from_wire() {
+        let states = wire(&[
+            xdg_toplevel::State::Maximized as u32,
+            xdg_toplevel::State::Activated as u32,
+            xdg_toplevel::State::TiledLeft as u32,
+        ]);
+
+        assert_eq!(
+            WindowState::from_wire(&states),
+            WindowState {
+                maximized: true,
+                activated: true,
+                tiled_left: true,
+                ..WindowState::default()
+            }
+        );
+    }
+
+    #[test]
+    fn window_state_from_empty_wire() {
+        assert_eq!(WindowState::from_wire(&[]), WindowState::default());
+    }
+
+    #[test]
+    fn window_state_skips_unknown_values_and_partial_chunks() {
+        let mut states = wire(&[1000, xdg_toplevel::State::Fullscreen as u32]);
+        states.extend_from_slice(&[xdg_toplevel::State::Suspended as u8, 0]);
+
+        assert_eq!(
+            WindowState::from_wire(&states),
+            WindowState {
+                fullscreen: true,
+                ..WindowState::default()
+            }
+        );
+    }
+
+    #[test]
+    fn wm_capabilities_from_wire() {
+        let capabilities = wire(&[
+            xdg_toplevel::WmCapabilities::Maximize as u32,
+            xdg_toplevel::WmCapabilities::Minimize as u32,
+        ]);
+
+        assert_eq!(
+            WmCapabilities::from_wire(&capabilities),
+            WmCapabilities {
+                window_menu: false,
+                maximize: true,
+                fullscreen: false,
+                minimize: true,
+            }
+        );
+    }
+
+    #[test]
+    fn wm_capabilities_from_empty_wire_supports_nothing() {
+        assert_eq!(
+            WmCapabilities::from_wire(&[]),
+            WmCapabilities {
+                window_menu: false,
+                maximize: false,
+                fullscreen: false,
+                minimize: false,
+            }
+        );
+    }
+
+    #[test]
+    fn wm_capabilities_skip_unknown_values_and_partial_chunks() {
+        let mut capabilities = wire(&[xdg_toplevel::WmCapabilities::WindowMenu as u32, 1000]);
+        capabilities.extend_from_slice(&[xdg_toplevel::WmCapabilities::Fullscreen as u8]);
+
+        assert_eq!(
+            WmCapabilities::from_wire(&capabilities),
+            WmCapabilities {
+                window_menu: true,
+                maximize: false,
+                fullscreen: false,
+                minimize: false,
+            }
+        );
+    }
+}